@@ -11,20 +11,13 @@ use twilight_model::id::Id;
 use vesper::framework::DefaultError;
 use vesper::prelude::*;
 
-// The framework accepts custom error types, however, the custom error must implement
-// `From<ParseError>`
+// The framework accepts custom error types, the errors raised by the framework itself, like
+// arguments failing to parse, are given to the hooks inside a `FrameworkError`.
 pub enum MyError {
-    Parse(ParseError),
     Http(twilight_http::Error),
     Other(DefaultError)
 }
 
-impl From<ParseError> for MyError {
-    fn from(value: ParseError) -> Self {
-        Self::Parse(value)
-    }
-}
-
 impl From<twilight_http::Error> for MyError {
     fn from(value: twilight_http::Error) -> Self {
         Self::Http(value)
@@ -89,20 +82,21 @@ async fn main() {
 #[after]
 async fn after_hook(
     _: &SlashContext<()>,
-    command_name: &str,
-    result: Option<Result<ElapsedTime, MyError>>
+    info: &CommandInfo<(), ElapsedTime, MyError>,
+    result: Option<Result<ElapsedTime, FrameworkError<MyError>>>
 ) {
     // We don't have a custom error handler, so result will be always `Some`
     let result = result.unwrap();
 
     match result {
         Ok(elapsed) => {
-            println!("Command {} took {} ms to execute", command_name, elapsed.0.as_millis())
+            println!("Command {} took {} ms to execute", info.path(), elapsed.0.as_millis())
         },
         Err(e) => match e {
-            MyError::Parse(p) => println!("An error occurred when parsing a command {}", p),
-            MyError::Http(e) => println!("An HTTP error occurred: {}", e),
-            MyError::Other(other) => println!("An error occurred {}", other)
+            FrameworkError::User(MyError::Http(e)) => println!("An HTTP error occurred: {}", e),
+            FrameworkError::User(MyError::Other(other)) => println!("An error occurred {}", other),
+            FrameworkError::Parse(p) => println!("An error occurred when parsing a command {}", p),
+            _ => println!("The framework failed to execute the command")
         }
     };
 }
@@ -55,8 +55,9 @@ async fn main() {
 }
 
 #[before]
-async fn before_hook(_ctx: &SlashContext<()>, command_name: &str) -> bool {
-    println!("Before hook executed for command {command_name}");
+async fn before_hook(_ctx: &SlashContext<()>, info: &CommandInfo<()>) -> bool {
+    // The path includes the parent and group of subcommands, like `config set`.
+    println!("Before hook executed for command {}", info.path());
     // The return type of this function specifies if the actual command should run or not, if `false`
     // is returned, then the command won't execute.
     true
@@ -65,8 +66,8 @@ async fn before_hook(_ctx: &SlashContext<()>, command_name: &str) -> bool {
 // The result field will be some only if the command returned no errors or if the command has
 // no custom error handler set.
 #[after]
-async fn after_hook(_ctx: &SlashContext<()>, command_name: &str, result: Option<DefaultCommandResult>) {
-    println!("{command_name} finished, returned value: {result:?}");
+async fn after_hook(_ctx: &SlashContext<()>, info: &CommandInfo<()>, result: Option<DefaultFrameworkResult>) {
+    println!("{} finished, returned value: {result:?}", info.path());
 }
 
 #[command]
@@ -88,8 +89,8 @@ async fn hello(ctx: &SlashContext<()>) -> DefaultCommandResult {
 }
 
 #[error_handler]
-async fn handle_error(_ctx: &SlashContext<()>, result: DefaultError) {
-    println!("Command had an error: {result:?}");
+async fn handle_error(_ctx: &SlashContext<()>, info: &CommandInfo<()>, result: FrameworkError<DefaultError>) {
+    println!("Command {} had an error: {result:?}", info.path());
 }
 
 #[command]
@@ -15,12 +15,15 @@ pub fn after(input: TokenStream2) -> Result<TokenStream2> {
     } = fun;
 
     match sig.inputs.len() {
-        c if c != 3 => {
+        // The denial of the checks is optional, so it is added if not present.
+        3 => sig.inputs.push(parse2(quote::quote!(_: ::std::option::Option<::vesper::check::Denial>))?),
+        4 => (),
+        _ => {
             // This hook is expected to have three arguments, a reference to an `SlashContext`,
-            // a &str indicating the name of the command and the result of a command execution.
-            return Err(Error::new(sig.inputs.span(), "Expected three arguments"));
+            // a `&CommandInfo` describing the command and the result of a command execution,
+            // and optionally the reason a check denied the execution.
+            return Err(Error::new(sig.inputs.span(), "Expected three or four arguments"));
         }
-        _ => (),
     };
 
     // The name of the original function
@@ -49,7 +52,7 @@ pub fn after(input: TokenStream2) -> Result<TokenStream2> {
         -> #path<
             #ty,
             <<#result_type as #optional>::Inner as #returnable>::Ok,
-            <<#result_type as #optional>::Inner as #returnable>::Err
+            <<<#result_type as #optional>::Inner as #returnable>::Err as ::vesper::extract::UserError>::Inner
         > {
             #path(#fn_ident)
         }
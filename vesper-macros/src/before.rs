@@ -14,11 +14,11 @@ pub fn before(input: TokenStream2) -> Result<TokenStream2> {
         block,
     } = fun;
 
-    if sig.inputs.len() > 2 {
-        // This hook is expected to have a `&SlashContext` and a `&str` parameter.
+    if sig.inputs.len() != 2 {
+        // This hook is expected to have a `&SlashContext` and a `&CommandInfo` parameter.
         return Err(Error::new(
             sig.inputs.span(),
-            "Function parameter must only be &SlashContext and &str",
+            "Function parameter must only be &SlashContext and &CommandInfo",
         ));
     }
 
@@ -34,12 +34,13 @@ pub fn before(input: TokenStream2) -> Result<TokenStream2> {
     util::check_return_type(&sig.output, quote::quote!(bool))?;
 
     let ty = util::get_context_type(&sig, true)?;
+    let (output, error) = util::get_info_types(sig.inputs.iter().nth(1).unwrap())?;
     // Get the hook macro so we can fit the function into a normal fn pointer
     let hook = util::get_hook_macro();
     let path = quote::quote!(::vesper::hook::BeforeHook);
 
     Ok(quote::quote! {
-        pub fn #ident() -> #path<#ty> {
+        pub fn #ident() -> #path<#ty, #output, #error> {
             #path(#fn_ident)
         }
 
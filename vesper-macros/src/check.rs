@@ -33,13 +33,21 @@ pub fn check(input: TokenStream2) -> Result<TokenStream2> {
     let hook = util::get_hook_macro();
     let path = quote::quote!(::vesper::hook::CheckHook);
 
+    // Checks can return either a `bool` or a `CheckOutcome`, so the output is converted into the
+    // result expected by the framework.
+    let error = quote::quote!(<#return_type as #returnable>::Err);
+    sig.output = parse2(quote::quote!(-> ::std::result::Result<::vesper::check::CheckOutcome, #error>))?;
+
     Ok(quote::quote! {
-        pub fn #ident() -> #path<#ty, <#return_type as #returnable>::Err> {
-            #path(#fn_ident)
+        pub fn #ident() -> #path<#ty, #error> {
+            #path::new(#fn_ident)
         }
 
         #[#hook]
         #(#attrs)*
-        #vis #sig #block
+        #vis #sig {
+            let __output: #return_type = async move #block.await;
+            ::vesper::check::IntoCheckResult::into_check_result(__output)
+        }
     })
 }
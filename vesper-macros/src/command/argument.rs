@@ -92,7 +92,7 @@ impl Argument {
             return Err(Error::new(
                 arg.span(),
                 "Missing `description`"
-            )).map_err(From::from);
+            ).into());
         }
 
         Ok(this)
@@ -7,7 +7,7 @@ use syn::parse::Parse;
 use syn::{Attribute, Result};
 use syn::punctuated::Punctuated;
 
-use crate::extractors::{Either, FixedList, FunctionPath, Ident, List, Map};
+use crate::extractors::{CheckExpr, Either, FixedList, FunctionPath, Ident, LayerExpr, List, Map};
 use crate::extractors::function_closure::FunctionOrClosure;
 
 #[derive(Default, FromMeta)]
@@ -28,13 +28,25 @@ pub struct CommandDetails {
     #[darling(default)]
     pub required_permissions: Option<List<Ident>>,
     #[darling(default)]
-    pub checks: Either<List<FunctionPath>, Punctuated<FunctionPath, Token![,]>>,
+    pub required_bot_permissions: Option<List<Ident>>,
+    #[darling(default)]
+    pub checks: Either<List<CheckExpr>, Punctuated<CheckExpr, Token![,]>>,
     #[darling(default)]
     pub error_handler: Option<Either<FunctionPath, FixedList<1, FunctionPath>>>,
     #[darling(default)]
     pub nsfw: bool,
     #[darling(default)]
-    pub only_guilds: bool
+    pub only_guilds: bool,
+    #[darling(default)]
+    pub cooldown: Option<CooldownOptions>,
+    #[darling(default)]
+    pub concurrency: Option<ConcurrencyOptions>,
+    #[darling(default)]
+    pub timeout: Option<TimeoutOptions>,
+    #[darling(default)]
+    pub defer: Option<DeferOptions>,
+    #[darling(default)]
+    pub layers: List<LayerExpr>
 }
 
 impl CommandDetails {
@@ -47,11 +59,41 @@ impl CommandDetails {
 
         let mut this = Self::from_list(meta.as_slice())?;
 
+        if let Some(cooldown) = &this.cooldown {
+            cooldown.validate()?;
+        }
+
+        if let Some(concurrency) = &this.concurrency {
+            concurrency.validate()?;
+        }
+
+        if let Some(timeout) = &this.timeout {
+            timeout.validate()?;
+        }
+
         this.input_options = input_options;
         Ok(this)
     }
 }
 
+/// Joins the given permissions into a single `Permissions` expression.
+fn permission_stream(permissions: &List<Ident>) -> TokenStream2 {
+    let mut permission_stream = TokenStream2::new();
+
+    for (index, permission) in permissions.iter().enumerate() {
+        if index == 0 || permissions.len() == 1 {
+            permission_stream
+                .extend(quote::quote!(vesper::twilight_exports::Permissions::#permission))
+        } else {
+            permission_stream.extend(
+                quote::quote!( | vesper::twilight_exports::Permissions::#permission),
+            )
+        }
+    }
+
+    permission_stream
+}
+
 impl ToTokens for CommandDetails {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let options = &self.input_options;
@@ -81,20 +123,13 @@ impl ToTokens for CommandDetails {
         }
 
         if let Some(permissions) = &self.required_permissions {
-            let mut permission_stream = TokenStream2::new();
-
-            for (index, permission) in permissions.iter().enumerate() {
-                if index == 0 || permissions.len() == 1 {
-                    permission_stream
-                        .extend(quote::quote!(vesper::twilight_exports::Permissions::#permission))
-                } else {
-                    permission_stream.extend(
-                        quote::quote!( | vesper::twilight_exports::Permissions::#permission),
-                    )
-                }
-            }
+            let permissions = permission_stream(permissions);
+            tokens.extend(quote::quote!(.required_permissions(#permissions)));
+        }
 
-            tokens.extend(quote::quote!(.required_permissions(#permission_stream)));
+        if let Some(permissions) = &self.required_bot_permissions {
+            let permissions = permission_stream(permissions);
+            tokens.extend(quote::quote!(.required_bot_permissions(#permissions)));
         }
 
         let mut checks = Vec::new();
@@ -105,7 +140,7 @@ impl ToTokens for CommandDetails {
         );
 
         tokens.extend(quote::quote! {
-            .checks(vec![#(#checks()),*])
+            .checks(vec![#(#checks),*])
         });
 
         if let Some(error_handler) = &self.error_handler {
@@ -120,6 +155,238 @@ impl ToTokens for CommandDetails {
             .nsfw(#nsfw)
             .only_guilds(#only_guilds)
         ));
+
+        if let Some(cooldown) = &self.cooldown {
+            tokens.extend(quote::quote!(.cooldown(#cooldown)));
+        }
+
+        if let Some(concurrency) = &self.concurrency {
+            tokens.extend(quote::quote!(.concurrency(#concurrency)));
+        }
+
+        if let Some(timeout) = &self.timeout {
+            tokens.extend(quote::quote!(.timeout(#timeout)));
+        }
+
+        if let Some(defer) = &self.defer {
+            tokens.extend(quote::quote!(.defer(#defer)));
+        }
+
+        for layer in self.layers.iter() {
+            tokens.extend(quote::quote!(.layer(#layer)));
+        }
+    }
+}
+
+#[derive(Default, FromMeta)]
+pub struct CooldownOptions {
+    #[darling(default)]
+    pub user: bool,
+    #[darling(default)]
+    pub member: bool,
+    #[darling(default)]
+    pub channel: bool,
+    #[darling(default)]
+    pub guild: bool,
+    #[darling(default)]
+    pub global: bool,
+    #[darling(default)]
+    pub uses: Option<u32>,
+    #[darling(default)]
+    pub seconds: Option<u64>,
+    #[darling(default)]
+    pub millis: Option<u64>
+}
+
+impl CooldownOptions {
+    fn validate(&self) -> Result<()> {
+        let buckets = [self.user, self.member, self.channel, self.guild, self.global];
+
+        if buckets.iter().filter(|selected| **selected).count() > 1 {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "Only one of `user`, `member`, `channel`, `guild` or `global` can be selected"
+            ));
+        }
+
+        if self.uses == Some(0) {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "A cooldown must allow at least one use"
+            ));
+        }
+
+        if self.duration_millis() == 0 {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "A cooldown requires a duration, specified using `seconds` or `millis`"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn duration_millis(&self) -> u64 {
+        self.seconds.unwrap_or_default() * 1000 + self.millis.unwrap_or_default()
+    }
+}
+
+impl ToTokens for CooldownOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let bucket = if self.member {
+            quote::quote!(Member)
+        } else if self.channel {
+            quote::quote!(Channel)
+        } else if self.guild {
+            quote::quote!(Guild)
+        } else if self.global {
+            quote::quote!(Global)
+        } else {
+            quote::quote!(User)
+        };
+
+        let uses = self.uses.unwrap_or(1);
+        let millis = self.duration_millis();
+
+        tokens.extend(quote::quote!(::vesper::cooldown::Cooldown::new(
+            #uses,
+            ::std::time::Duration::from_millis(#millis),
+            ::vesper::cooldown::BucketKind::#bucket
+        )));
+    }
+}
+
+#[derive(Default, FromMeta)]
+pub struct ConcurrencyOptions {
+    #[darling(default)]
+    pub user: bool,
+    #[darling(default)]
+    pub member: bool,
+    #[darling(default)]
+    pub channel: bool,
+    #[darling(default)]
+    pub guild: bool,
+    #[darling(default)]
+    pub global: bool,
+    #[darling(default)]
+    pub limit: Option<u32>,
+    #[darling(default)]
+    pub queue: bool
+}
+
+impl ConcurrencyOptions {
+    fn validate(&self) -> Result<()> {
+        let buckets = [self.user, self.member, self.channel, self.guild, self.global];
+
+        if buckets.iter().filter(|selected| **selected).count() > 1 {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "Only one of `user`, `member`, `channel`, `guild` or `global` can be selected"
+            ));
+        }
+
+        if self.limit == Some(0) {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "A concurrency limit must allow at least one execution"
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl ToTokens for ConcurrencyOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let bucket = if self.member {
+            quote::quote!(Member)
+        } else if self.channel {
+            quote::quote!(Channel)
+        } else if self.guild {
+            quote::quote!(Guild)
+        } else if self.global {
+            quote::quote!(Global)
+        } else {
+            quote::quote!(User)
+        };
+
+        let limit = self.limit.unwrap_or(1);
+
+        tokens.extend(quote::quote!(::vesper::concurrency::Concurrency::new(
+            #limit,
+            ::vesper::cooldown::BucketKind::#bucket
+        )));
+
+        if self.queue {
+            tokens.extend(quote::quote!(.queue()));
+        }
+    }
+}
+
+#[derive(Default, FromMeta)]
+pub struct TimeoutOptions {
+    #[darling(default)]
+    pub seconds: Option<u64>,
+    #[darling(default)]
+    pub millis: Option<u64>
+}
+
+impl TimeoutOptions {
+    fn validate(&self) -> Result<()> {
+        if self.duration_millis() == 0 {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "A timeout requires a duration, specified using `seconds` or `millis`"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn duration_millis(&self) -> u64 {
+        self.seconds.unwrap_or_default() * 1000 + self.millis.unwrap_or_default()
+    }
+}
+
+impl ToTokens for TimeoutOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let millis = self.duration_millis();
+        tokens.extend(quote::quote!(::std::time::Duration::from_millis(#millis)));
+    }
+}
+
+/// The options of the `#[defer]` attribute, which can be used as `#[defer]` or `#[defer(ephemeral)]`.
+#[derive(Default)]
+pub struct DeferOptions {
+    pub ephemeral: bool
+}
+
+impl FromMeta for DeferOptions {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut this = Self::default();
+
+        for item in items {
+            match item {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("ephemeral") => this.ephemeral = true,
+                other => return Err(darling::Error::custom("Only `ephemeral` is allowed").with_span(other))
+            }
+        }
+
+        Ok(this)
+    }
+}
+
+impl ToTokens for DeferOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.extend(quote::quote!(::vesper::defer::Defer::immediate()));
+
+        if self.ephemeral {
+            tokens.extend(quote::quote!(.ephemeral()));
+        }
     }
 }
 
@@ -1,5 +1,5 @@
 mod argument;
-mod details;
+pub(crate) mod details;
 
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use syn::{parse2, spanned::Spanned, Block, Error, ItemFn, Result, Signature, Type};
@@ -45,11 +45,13 @@ pub fn command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenSt
     let extract_output = util::get_hook_macro();
     let command_path = util::get_command_path();
 
+    util::wrap_framework_error(&mut sig, &mut block, &output)?;
     let args = parse_arguments(
         &mut sig,
         &mut block,
         context_ident,
-        input_options.chat
+        input_options.chat,
+        input_options.user
     )?;
     let opts = CommandDetails::parse(input_options, &mut attrs)?;
 
@@ -71,7 +73,8 @@ pub fn parse_arguments(
     sig: &mut Signature,
     block: &mut Block,
     ctx_ident: Ident,
-    chat_command: bool
+    chat_command: bool,
+    user_command: bool
 ) -> Result<Vec<Argument>> {
     let mut arguments = Vec::new();
     while sig.inputs.len() > 1 {
@@ -89,7 +92,7 @@ pub fn parse_arguments(
         arguments
             .iter()
             .map(|s| {
-                if let Some(renaming) = s.attributes.as_ref().map(|a| a.renaming.as_ref()).flatten() {
+                if let Some(renaming) = s.attributes.as_ref().and_then(|a| a.renaming.as_ref()) {
                     renaming.inner().clone()
                 } else {
                     s.ident.to_string()
@@ -102,18 +105,44 @@ pub fn parse_arguments(
         // The original block of the function
         let b = &block;
 
+        // The first argument of user commands receives the target of the command
+        let target = if user_command {
+            let (name, ty) = (names[0], types[0]);
+            Some(quote::quote!(let #name = __options.target_parse::<#ty>().await?;))
+        } else {
+            None
+        };
+        let skip = target.is_some() as usize;
+        let (parsed_names, parsed_types, parsed_renames) = (
+            &names[skip..],
+            &types[skip..],
+            &renames[skip..]
+        );
+
         // Modify the block to parse arguments
         *block = parse2(quote::quote! {{
             let (#(#names),*) = {
                 let mut __options = ::vesper::iter::DataIterator::new(#ctx_ident);
 
-                #(let #names =
-                    __options.named_parse::<#types>(#renames).await?;)*
+                #target
+
+                #(let #parsed_names =
+                    __options.named_parse::<#parsed_types>(#parsed_renames).await?;)*
 
                 if __options.len() > 0 {
-                    return Err(
-                        ::vesper::prelude::ParseError::StructureMismatch("Too many arguments received".to_string()).into()
-                    );
+                    return Err(::vesper::error::FrameworkError::Parse(
+                        ::vesper::prelude::ParseError::StructureMismatch("Too many arguments received".to_string())
+                    ));
+                }
+
+                {
+                    // Record the parsed values so the hooks can see what the command received.
+                    use ::vesper::argument::{RecordDebug as _, RecordOpaque as _, RecordValue as _};
+
+                    #ctx_ident.record_arguments(vec![#(::vesper::argument::ParsedArgument::new(
+                        #renames,
+                        (&&&::vesper::argument::Record(&#names)).record()
+                    )),*]);
                 }
 
                 (#(#names),*)
@@ -0,0 +1,219 @@
+use darling::{export::NestedMeta, FromMeta};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{parse2, punctuated::Punctuated, spanned::Spanned, Attribute, Error, FnArg, ItemFn, Result, Token, Type};
+
+use crate::command::details::MetaListParser;
+use crate::extractors::{CheckExpr, Either, FixedList, FunctionPath, List};
+use crate::util;
+
+/// The options given to the component macro.
+#[derive(Default, FromMeta)]
+struct ComponentOptions {
+    #[darling(default)]
+    modal: bool,
+    #[darling(default)]
+    prefix: Option<String>,
+    #[darling(default)]
+    pattern: Option<String>,
+    #[darling(default)]
+    state: bool,
+}
+
+impl ComponentOptions {
+    fn new(stream: TokenStream2) -> Result<Self> {
+        let span = stream.span();
+
+        if let Ok(prefix) = parse2::<syn::LitStr>(stream.clone()) {
+            return Ok(Self {
+                prefix: Some(prefix.value()),
+                ..Default::default()
+            });
+        }
+
+        let meta = parse2::<MetaListParser>(stream)?.0
+            .into_iter()
+            .map(NestedMeta::Meta)
+            .collect::<Vec<_>>();
+
+        let this = Self::from_list(&meta)?;
+
+        let routes = [this.prefix.is_some(), this.pattern.is_some(), this.state];
+
+        if routes.iter().filter(|provided| **provided).count() != 1 {
+            return Err(Error::new(span, "Exactly one of `prefix`, `pattern` or `state` must be provided"));
+        }
+
+        if this.pattern.as_ref().map(|p| p.matches('*').count() > 1).unwrap_or(false) {
+            return Err(Error::new(span, "A route pattern can only contain a single `*`"));
+        }
+
+        Ok(this)
+    }
+}
+
+/// The attributes a component handler can have.
+#[derive(Default, FromMeta)]
+struct ComponentDetails {
+    #[darling(default)]
+    checks: Either<List<CheckExpr>, Punctuated<CheckExpr, Token![,]>>,
+    #[darling(default)]
+    error_handler: Option<Either<FunctionPath, FixedList<1, FunctionPath>>>,
+}
+
+impl ComponentDetails {
+    fn parse(attrs: &mut Vec<Attribute>) -> Result<Self> {
+        let mut meta = Vec::new();
+        let mut index = 0;
+
+        // Only take the attributes used by the macro, leaving the rest in the function.
+        while index < attrs.len() {
+            if attrs[index].path().is_ident("checks") || attrs[index].path().is_ident("error_handler") {
+                meta.push(NestedMeta::Meta(attrs.remove(index).meta));
+            } else {
+                index += 1;
+            }
+        }
+
+        Ok(Self::from_list(&meta)?)
+    }
+}
+
+/// The implementation of the component macro, this macro takes the given function and wraps it
+/// into a component handler, routed by the prefix or pattern provided. The second parameter of the
+/// function receives the remainder of the custom id, parsed using [`FromStr`] unless it is a `&str`.
+///
+/// [`FromStr`]: std::str::FromStr
+pub fn component(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    let fun = parse2::<ItemFn>(input)?;
+    let ItemFn {
+        mut attrs,
+        vis,
+        mut sig,
+        mut block,
+    } = fun;
+
+    if sig.inputs.is_empty() || sig.inputs.len() > 2 {
+        // This handler is expected to have a `&SlashContext` and, optionally, the remainder.
+        return Err(Error::new(
+            sig.inputs.span(),
+            "Function parameters must be &SlashContext and, optionally, the custom id remainder",
+        ));
+    }
+
+    let options = ComponentOptions::new(macro_attrs)?;
+    let details = ComponentDetails::parse(&mut attrs)?;
+
+    // The name of the original function
+    let ident = sig.ident.clone();
+    // The name the function will have after this macro's execution
+    let fn_ident = quote::format_ident!("_{}", &ident);
+    sig.ident = fn_ident.clone();
+
+    let ty = util::get_context_type(&sig, true)?;
+    let output = util::get_return_type(&sig)?;
+    let returnable = util::get_returnable_trait();
+
+    let ctx_ident = util::get_ident(&util::get_pat(sig.inputs.first().unwrap())?.pat)?;
+    let mut state_type = None;
+
+    util::wrap_framework_error(&mut sig, &mut block, &output)?;
+
+    // Make the function receive the remainder as a `&str`, parsing it if another type is required.
+    match sig.inputs.pop().map(|arg| arg.into_value()) {
+        Some(FnArg::Typed(arg)) if sig.inputs.len() == 1 && options.state => {
+            let pat = &arg.pat;
+            let kind = &arg.ty;
+            let b = &block;
+
+            sig.inputs.push(parse2(quote::quote!(_: &str))?);
+            *block = parse2(quote::quote! {{
+                let #pat: #kind = #ctx_ident.custom_ids
+                    .decode_interaction::<#kind>(&#ctx_ident.interaction)
+                    .map_err(|why| ::vesper::prelude::ParseError::Other(Box::new(why)))?;
+
+                #b
+            }})?;
+
+            state_type = Some(kind.clone());
+        }
+        Some(FnArg::Typed(arg)) if sig.inputs.len() == 1 => {
+            let is_str = matches!(
+                &*arg.ty,
+                Type::Reference(r) if matches!(&*r.elem, Type::Path(p) if p.path.is_ident("str"))
+            );
+
+            if is_str {
+                sig.inputs.push(FnArg::Typed(arg));
+            } else {
+                let pat = &arg.pat;
+                let kind = &arg.ty;
+                let b = &block;
+
+                sig.inputs.push(parse2(quote::quote!(__remainder: &str))?);
+                *block = parse2(quote::quote! {{
+                    let #pat: #kind = <#kind as ::std::str::FromStr>::from_str(__remainder)
+                        .map_err(|why| ::vesper::prelude::ParseError::Parsing {
+                            argument_name: String::from("custom_id"),
+                            required: true,
+                            argument_type: String::from(stringify!(#kind)),
+                            error: why.to_string(),
+                        })?;
+
+                    #b
+                }})?;
+            }
+        }
+        Some(arg) => {
+            // Only the context was provided, so add an unused remainder parameter.
+            sig.inputs.push(arg);
+            sig.inputs.push(parse2(quote::quote!(_: &str))?);
+        }
+        None => unreachable!(),
+    }
+
+    let route = match (&options.prefix, &options.pattern, &state_type) {
+        (Some(prefix), ..) => quote::quote!(::vesper::component::ComponentRoute::prefix(#prefix)),
+        (_, Some(pattern), _) => quote::quote!(::vesper::component::ComponentRoute::pattern(#pattern)),
+        (.., Some(state)) => quote::quote!(::vesper::component::ComponentRoute::prefix(
+            <#state as ::vesper::custom_id::CustomId>::PREFIX
+        )),
+        _ => return Err(Error::new(sig.inputs.span(), "The `state` route requires a state parameter")),
+    };
+
+    let kind = if options.modal {
+        quote::quote!(::vesper::component::ComponentKind::Modal)
+    } else {
+        quote::quote!(::vesper::component::ComponentKind::Component)
+    };
+
+    let mut checks = Vec::new();
+    details.checks.map_1(
+        &mut checks,
+        |checks, a| checks.extend(a.iter().cloned()),
+        |checks, b| checks.extend(b.iter().cloned())
+    );
+
+    let error_handler = details.error_handler.as_ref().map(|handler| {
+        let handler = handler.inner();
+        quote::quote!(.error_handler(#handler()))
+    });
+
+    let name = ident.to_string();
+    let hook = util::get_hook_macro();
+    let path = quote::quote!(::vesper::component::ComponentHandler);
+
+    Ok(quote::quote! {
+        pub fn #ident() -> #path<#ty, <#output as #returnable>::Ok, <#output as #returnable>::Err> {
+            #path::new(#fn_ident)
+                .name(#name)
+                .kind(#kind)
+                .route(#route)
+                .checks(vec![#(#checks),*])
+                #error_handler
+        }
+
+        #[#hook]
+        #(#attrs)*
+        #vis #sig #block
+    })
+}
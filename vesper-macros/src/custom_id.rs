@@ -0,0 +1,116 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, Result};
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(custom_id))]
+struct CustomIdAttributes {
+    #[darling(default)]
+    namespace: Option<String>,
+}
+
+pub fn custom_id(input: TokenStream2) -> Result<TokenStream2> {
+    let derive = syn::parse2::<DeriveInput>(input)?;
+    let attributes = CustomIdAttributes::from_derive_input(&derive)?;
+
+    let namespace = attributes.namespace.unwrap_or_else(|| derive.ident.to_string());
+
+    if namespace.is_empty() || namespace.contains(':') {
+        return Err(Error::new(
+            derive.ident.span(),
+            "The namespace must not be empty nor contain `:`",
+        ));
+    }
+
+    let prefix = format!("{}:", namespace);
+
+    let (write, read) = match &derive.data {
+        Data::Struct(data) => struct_tokens(&data.fields),
+        Data::Enum(data) => {
+            let mut write = TokenStream2::new();
+            let mut read = TokenStream2::new();
+
+            for (index, variant) in data.variants.iter().enumerate() {
+                if !matches!(&variant.fields, Fields::Unit) {
+                    return Err(Error::new(
+                        variant.span(),
+                        "Only enums without inner values can be used as custom ids",
+                    ));
+                }
+
+                let ident = &variant.ident;
+                write.extend(quote::quote!(Self::#ident => #index,));
+                read.extend(quote::quote!(#index => Ok(Self::#ident),));
+            }
+
+            (
+                quote::quote! {
+                    let index: usize = match self { #write };
+                    writer.write(&index);
+                },
+                quote::quote! {
+                    match reader.read::<usize>()? {
+                        #read
+                        other => Err(CustomIdError::InvalidField(other.to_string()))
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                derive.ident.span(),
+                "This derive is only available for structs and enums",
+            ))
+        }
+    };
+
+    let name = &derive.ident;
+    let (impl_generics, ty_generics, where_clause) = derive.generics.split_for_impl();
+
+    Ok(quote::quote! {
+        const _: () = {
+            use ::vesper::custom_id::{CustomId, CustomIdError, CustomIdReader, CustomIdWriter};
+
+            #[automatically_derived]
+            impl #impl_generics CustomId for #name #ty_generics #where_clause {
+                const NAMESPACE: &'static str = #namespace;
+                const PREFIX: &'static str = #prefix;
+
+                fn write(&self, writer: &mut CustomIdWriter) {
+                    #write
+                }
+
+                fn read(reader: &mut CustomIdReader<'_>) -> Result<Self, CustomIdError> {
+                    #read
+                }
+            }
+        };
+    })
+}
+
+/// Generates the tokens used to write and read the fields of a struct.
+fn struct_tokens(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+
+            (
+                quote::quote!(#(writer.write(&self.#idents);)*),
+                quote::quote!(Ok(Self { #(#idents: reader.read()?),* })),
+            )
+        }
+        Fields::Unnamed(unnamed) => {
+            let indexes = (0..unnamed.unnamed.len()).map(syn::Index::from).collect::<Vec<_>>();
+            let reads = indexes.iter().map(|_| quote::quote!(reader.read()?));
+
+            (
+                quote::quote!(#(writer.write(&self.#indexes);)*),
+                quote::quote!(Ok(Self(#(#reads),*))),
+            )
+        }
+        Fields::Unit => (
+            quote::quote!(let _ = writer;),
+            quote::quote!(let _ = reader; Ok(Self)),
+        ),
+    }
+}
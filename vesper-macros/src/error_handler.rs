@@ -11,13 +11,16 @@ pub fn error_handler(input: TokenStream2) -> Result<TokenStream2> {
         block,
     } = fun;
 
-    match sig.inputs.len() {
-        c if c != 2 => {
-            // This hook is expected to have three arguments, a reference to an `SlashContext`,
-            // a &str indicating the name of the command and the result of a command execution.
-            return Err(Error::new(sig.inputs.span(), "Expected two arguments"));
+    // The information about the command is optional, so handlers only taking the error keep
+    // working, accepting the information of commands returning any output type.
+    let generic_output = match sig.inputs.len() {
+        2 => true,
+        3 => false,
+        _ => {
+            // This hook is expected to have a reference to an `SlashContext`, optionally a
+            // `&CommandInfo` describing the command and the error raised by the command.
+            return Err(Error::new(sig.inputs.span(), "Expected two or three arguments"));
         }
-        _ => (),
     };
 
     // The name of the original function
@@ -32,17 +35,38 @@ pub fn error_handler(input: TokenStream2) -> Result<TokenStream2> {
     */
     util::check_return_type(&sig.output, quote::quote!(()))?;
 
-    let error_type = util::get_path(&util::get_pat(sig.inputs.iter().nth(1).unwrap())?.ty, false)?;
+    let error_type = util::get_path(&util::get_pat(sig.inputs.iter().last().unwrap())?.ty, false)?.clone();
 
     let ty = util::get_context_type(&sig, true)?;
     // Get the hook macro so we can fit the function into a normal fn pointer
     let hook = util::get_hook_macro();
     let path = quote::quote!(::vesper::hook::ErrorHandlerHook);
 
-    Ok(quote::quote! {
-        pub fn #ident() -> #path<#ty, #error_type> {
-            #path(#fn_ident)
+    let user_error = quote::quote!(<#error_type as ::vesper::extract::UserError>::Inner);
+
+    let pointer = if generic_output {
+        sig.generics.params.push(parse2(quote::quote!(__Output: 'static))?);
+        sig.inputs.insert(1, parse2(quote::quote!(
+            _: &::vesper::command::CommandInfo<'_, #ty, __Output, #user_error>
+        ))?);
+
+        quote::quote! {
+            pub fn #ident<__Output: 'static>() -> #path<#ty, __Output, #user_error> {
+                #path(#fn_ident::<__Output>)
+            }
+        }
+    } else {
+        let (output, _) = util::get_info_types(sig.inputs.iter().nth(1).unwrap())?;
+
+        quote::quote! {
+            pub fn #ident() -> #path<#ty, #output, #user_error> {
+                #path(#fn_ident)
+            }
         }
+    };
+
+    Ok(quote::quote! {
+        #pointer
 
         #[#hook]
         #(#attrs)*
@@ -0,0 +1,85 @@
+use darling::ast::NestedMeta;
+use darling::{Error, FromMeta};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{Meta, Path, Token};
+
+/// The combinators that can be used inside the `checks` attribute.
+const COMBINATORS: [&str; 3] = ["any_of", "all_of", "not"];
+
+/// A check used inside the `checks` attribute, which can be the path of a check function, a
+/// combinator of other checks or a call to a function returning a check.
+#[derive(Clone)]
+pub enum CheckExpr {
+    /// A check function, like `only_guilds`.
+    Path(Path),
+    /// A combinator of checks, like `any_of(only_guilds, owner_only)`.
+    Combinator(syn::Ident, Vec<CheckExpr>),
+    /// A function creating a check with arguments, like `in_channel(Id::new(1))`.
+    Call(Path, TokenStream2),
+}
+
+impl CheckExpr {
+    fn from_meta_item(meta: &Meta) -> darling::Result<Self> {
+        match meta {
+            Meta::Path(path) => Ok(Self::Path(path.clone())),
+            Meta::List(list) => {
+                let combinator = list
+                    .path
+                    .get_ident()
+                    .filter(|ident| COMBINATORS.iter().any(|name| *ident == name));
+
+                let Some(combinator) = combinator else {
+                    return Ok(Self::Call(list.path.clone(), list.tokens.clone()));
+                };
+
+                let inner = Punctuated::<NestedMeta, Token![,]>::parse_terminated
+                    .parse2(list.tokens.clone())
+                    .map_err(Error::from)?
+                    .iter()
+                    .map(Self::from_nested_meta)
+                    .collect::<darling::Result<Vec<_>>>()?;
+
+                if *combinator == "not" && inner.len() != 1 {
+                    return Err(Error::custom("`not` expects a single check").with_span(list));
+                }
+
+                Ok(Self::Combinator(combinator.clone(), inner))
+            }
+            Meta::NameValue(_) => Err(Error::custom("Expected a check").with_span(meta)),
+        }
+    }
+}
+
+impl FromMeta for CheckExpr {
+    fn from_nested_meta(item: &NestedMeta) -> darling::Result<Self> {
+        match item {
+            NestedMeta::Meta(meta) => Self::from_meta_item(meta),
+            NestedMeta::Lit(lit) => Err(Error::unexpected_lit_type(lit)),
+        }
+    }
+}
+
+impl Parse for CheckExpr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Self::from_meta_item(&input.parse::<Meta>()?).map_err(syn::Error::from)
+    }
+}
+
+impl ToTokens for CheckExpr {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Path(path) => tokens.extend(quote::quote!(#path())),
+            Self::Combinator(combinator, inner) if *combinator == "not" => {
+                let inner = &inner[0];
+                tokens.extend(quote::quote!(::vesper::checks::not(#inner)))
+            }
+            Self::Combinator(combinator, inner) => {
+                tokens.extend(quote::quote!(::vesper::checks::#combinator(vec![#(#inner),*])))
+            }
+            Self::Call(path, arguments) => tokens.extend(quote::quote!(#path(#arguments))),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use darling::ast::NestedMeta;
+use darling::{Error, FromMeta};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens;
+use syn::{Meta, Path};
+
+/// A layer used inside the `layers` attribute, which can be the path of a value implementing
+/// `Layer`, like `Logging`, or a call to a function returning one, like `RateLimit::new(5)`.
+#[derive(Clone)]
+pub enum LayerExpr {
+    Path(Path),
+    Call(Path, TokenStream2),
+}
+
+impl FromMeta for LayerExpr {
+    fn from_nested_meta(item: &NestedMeta) -> darling::Result<Self> {
+        match item {
+            NestedMeta::Meta(Meta::Path(path)) => Ok(Self::Path(path.clone())),
+            NestedMeta::Meta(Meta::List(list)) => Ok(Self::Call(list.path.clone(), list.tokens.clone())),
+            NestedMeta::Meta(meta) => Err(Error::custom("Expected a layer").with_span(meta)),
+            NestedMeta::Lit(lit) => Err(Error::unexpected_lit_type(lit)),
+        }
+    }
+}
+
+impl ToTokens for LayerExpr {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Path(path) => path.to_tokens(tokens),
+            Self::Call(path, arguments) => tokens.extend(quote::quote!(#path(#arguments))),
+        }
+    }
+}
@@ -1,16 +1,20 @@
+pub mod check;
 pub mod closure;
 pub mod either;
 pub mod function_closure;
 pub mod function_path;
 pub mod ident;
+pub mod layer;
 pub mod list;
 pub mod map;
 pub mod tuple;
 
 pub use {
+    check::*,
     either::*,
     function_path::*,
     ident::*,
+    layer::*,
     list::*,
     map::*,
     tuple::*
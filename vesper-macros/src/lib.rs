@@ -8,6 +8,9 @@ mod before;
 mod check;
 mod extractors;
 mod command;
+mod component;
+mod custom_id;
+mod cooldown_responder;
 mod error_handler;
 mod hook;
 mod modal;
@@ -94,6 +97,48 @@ pub fn hook(_: TokenStream, input: TokenStream) -> TokenStream {
 /// [twilight permissions](https://docs.rs/twilight-model/latest/twilight_model/guild/struct.Permissions.html).
 /// For example, to specify that a user needs to have administrator permissions to execute a command,
 /// the attribute would be used like this `#[required_permissions(ADMINISTRATOR)]`.
+///
+/// The permissions the bot needs to execute the command can be specified the same way using the
+/// `#[required_bot_permissions]` attribute. These are checked against the permissions of the
+/// bot in the channel every time the command is used, so the command doesn't execute if any of
+/// them is missing.
+///
+/// ## Cooldowns
+///
+/// A cooldown can be applied to the command using the `#[cooldown]` attribute. It accepts the
+/// bucket the cooldown applies to, which is one of `user`, `member`, `channel`, `guild` or
+/// `global`, defaulting to `user`, the amount of `uses` allowed in the period, defaulting to one,
+/// and the period itself, specified using `seconds`, `millis` or both. For example, to allow a
+/// command to be used three times per minute in every guild, the attribute would be used like this
+/// `#[cooldown(guild, uses = 3, seconds = 60)]`.
+///
+/// ## Concurrency limits
+///
+/// The amount of executions of the command running at the same time can be limited using the
+/// `#[concurrency]` attribute. It accepts the same buckets as `#[cooldown]`, defaulting to `user`,
+/// the `limit` of executions, defaulting to one, and `queue`, which makes the executions wait
+/// for the running ones to finish instead of being rejected. For example, to run a single
+/// execution at a time in every guild, queueing the rest, the attribute would be used like this
+/// `#[concurrency(guild, queue)]`.
+///
+/// ## Timeouts
+///
+/// The `#[timeout]` attribute sets the maximum time the command can run, overriding the timeout
+/// of the framework. It accepts the duration using `seconds`, `millis` or both, for example
+/// `#[timeout(seconds = 30)]`. Once it elapses the command is stopped and its error handler
+/// receives a `FrameworkError::TimedOut`.
+///
+/// ## Deferring
+///
+/// The `#[defer]` attribute makes the framework defer the interaction before the command runs,
+/// overriding the policy of the framework. The deferred response can be made ephemeral using
+/// `#[defer(ephemeral)]`.
+///
+/// ## Layers
+///
+/// The `#[layers]` attribute adds layers wrapping the execution of the command, inside the ones
+/// of the framework and its group. It accepts values implementing `Layer`, or calls to functions
+/// returning them, for example `#[layers(Logging, RateLimit::new(5))]`.
 #[proc_macro_attribute]
 pub fn command(attrs: TokenStream, input: TokenStream) -> TokenStream {
     extract(command::command(attrs.into(), input.into()))
@@ -123,6 +168,46 @@ pub fn error_handler(_: TokenStream, input: TokenStream) -> TokenStream {
     extract(error_handler::error_handler(input.into()))
 }
 
+/// Converts an `async` function into a handler of component or modal interactions, routed by
+/// their custom id.
+///
+/// The function must have as first parameter a `&mut SlashContext<T>`, and can optionally have a
+/// second parameter receiving the remainder of the custom id, which is the part not matched by the
+/// route. The remainder is parsed using `FromStr`, unless the parameter is a `&str`.
+///
+/// # Usage:
+///
+///     - Using a prefix, as #[component("vote:")], which matches all custom ids starting with `vote:`.
+///     - Using a pattern, as #[component(pattern = "ticket:*:close")], where `*` is the remainder.
+///     - Adding `modal`, as #[component(modal, prefix = "feedback:")], to receive modal submits instead.
+///     - Using a typed state, as #[component(state)], where the second parameter implements `CustomId`
+///       and is decoded from the whole custom id, verifying its signature if a key was set.
+///
+/// Handlers accept the `#[checks]` and `#[error_handler]` attributes, the same way commands do.
+///
+/// # Examples
+///
+/// ```rust
+/// use vesper::prelude::*;
+///
+/// #[component("vote:")]
+/// async fn vote(ctx: &mut SlashContext<()>, option: u32) -> DefaultCommandResult {
+///     // Register the vote for the given option
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn component(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    extract(component::component(attrs.into(), input.into()))
+}
+
+/// Prepares the function to be used to tell the user a command is on cooldown, see
+/// the implementation for more information about this macro's behaviour.
+#[proc_macro_attribute]
+pub fn cooldown_responder(_: TokenStream, input: TokenStream) -> TokenStream {
+    extract(cooldown_responder::cooldown_responder(input.into()))
+}
+
 /// Prepares the function to be used to autocomplete command arguments.
 #[proc_macro_attribute]
 pub fn autocomplete(_: TokenStream, input: TokenStream) -> TokenStream {
@@ -190,7 +275,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 /// The derive macro accepts several attributes:
 ///
 /// - `#[modal(title = "<TITLE>")]`: This attribute allows specifying the title of the modal, by default the
-/// title will be the name of the structure.
+///   title will be the name of the structure.
 ///
 /// ## Example
 ///
@@ -208,7 +293,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 /// ```
 ///
 /// - `#[modal(label = "<LABEL>")]`: This attribute allows setting the label of the field, by default it will
-/// be the name of the struct field.
+///   be the name of the struct field.
 ///
 /// ## Example
 ///
@@ -224,7 +309,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 /// ```
 ///
 /// - `#[modal(max_length = x)]` and `#[modal(min_length = y)]`: These attributes allow to set a
-/// maximum/minimum amount of characters a field can have.
+///   maximum/minimum amount of characters a field can have.
 ///
 /// ## Example
 ///
@@ -242,7 +327,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 /// ```
 ///
 /// - #[modal(placeholder = "<PLACEHOLDER>")]: This attribute allows specifying a placeholder that will be seen
-/// before entering anything on the input.
+///   before entering anything on the input.
 /// 
 /// ## Example
 /// 
@@ -257,8 +342,8 @@ pub fn parse(input: TokenStream) -> TokenStream {
 /// ```
 /// 
 /// - `#[modal(paragraph)]`: This attribute will mark the field as a paragraph. By default, all fields are
-/// marked as single line fields, so the user will only be able to input up to one line unless we
-/// mark it as a paragraph.
+///   marked as single line fields, so the user will only be able to input up to one line unless we
+///   mark it as a paragraph.
 ///
 /// ## Example
 ///
@@ -280,6 +365,37 @@ pub fn modal(input: TokenStream) -> TokenStream {
     extract(modal::modal(input.into()))
 }
 
+/// Implements the `CustomId` trait for the derived struct or enum, allowing it to be encoded into
+/// the custom id of components and modals and decoded back.
+///
+/// All the fields of the struct must implement `CustomIdField`, which is implemented for integers,
+/// `bool`, `String`, ids and options of them. Enums can only have variants without inner values.
+///
+/// # Examples
+///
+/// ```rust
+/// use vesper::prelude::*;
+/// use twilight_model::id::{Id, marker::UserMarker};
+///
+/// #[derive(CustomId)]
+/// #[custom_id(namespace = "vote")]
+/// struct Vote {
+///     poll: u32,
+///     option: u8,
+///     voter: Option<Id<UserMarker>>
+/// }
+/// ```
+///
+/// # Attributes
+///
+/// - `#[custom_id(namespace = "<NAMESPACE>")]`: Sets the namespace the custom ids belong to, which
+///   is used to route them. By default the namespace is the name of the type, a short namespace
+///   leaves more room for the fields, as custom ids are limited to 100 characters.
+#[proc_macro_derive(CustomId, attributes(custom_id))]
+pub fn custom_id(input: TokenStream) -> TokenStream {
+    extract(custom_id::custom_id(input.into()))
+}
+
 /// Extracts the given result, throwing a compile error if an error is given.
 fn extract(res: syn::Result<TokenStream2>) -> TokenStream {
     match res {
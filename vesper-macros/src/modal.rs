@@ -49,7 +49,7 @@ impl Modal {
 
 impl Field {
     fn new(field: &syn::Field) -> darling::Result<Self> {
-        let mut this = Field::from_field(&field)?;
+        let mut this = Field::from_field(field)?;
         this.attributes = FieldAttributes::from_attributes(field.attrs.as_slice())?;
 
         if this.attributes.label.is_none() {
@@ -121,25 +121,19 @@ fn fields(data: &Data, derive_span: impl Spanned) -> Result<&FieldsNamed> {
     match data {
         Data::Struct(s) => match &s.fields {
             Fields::Named(fields) => Ok(fields),
-            Fields::Unnamed(fields) => {
-                return Err(Error::new(
-                    fields.span(),
-                    "Tuple structs not supported",
-                ))
-            },
-            Fields::Unit => {
-                return Err(Error::new(
-                    s.fields.span(),
-                    "Unit structs not supported",
-                ))
-            }
+            Fields::Unnamed(fields) => Err(Error::new(
+                fields.span(),
+                "Tuple structs not supported",
+            )),
+            Fields::Unit => Err(Error::new(
+                s.fields.span(),
+                "Unit structs not supported",
+            )),
         },
-        _ => {
-            return Err(Error::new(
-                derive_span.span(),
-                "This derive is only available for structs",
-            ))
-        }
+        _ => Err(Error::new(
+            derive_span.span(),
+            "This derive is only available for structs",
+        )),
     }
 }
 
@@ -52,9 +52,8 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
     };
 
     let mut variants = Vec::new();
-    let mut index = 1;
 
-    for variant in enumeration.variants {
+    for (index, variant) in (1..).zip(enumeration.variants) {
         if !matches!(&variant.fields, syn::Fields::Unit) {
             return Err(Error::new(
                 variant.span(),
@@ -73,8 +72,6 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
             value: name,
             index,
         });
-
-        index += 1;
     }
 
     let mut parse_stream = TokenStream2::new();
@@ -89,11 +86,9 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
     Ok(quote::quote! {
         const _: () = {
             use ::vesper::{
-                builder::WrappedClient,
                 prelude::async_trait,
-                parse::{Parse, ParseError},
+                parse::{Parse, ParseContext, ParseError},
                 twilight_exports::{
-                    CommandInteractionDataResolved,
                     CommandOptionChoice,
                     CommandOptionChoiceValue,
                     CommandOptionType,
@@ -105,14 +100,12 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
             #[automatically_derived]
             #[async_trait]
             impl<T: Send + Sync + 'static> Parse<T> for #enum_name {
-                async fn parse(
-                    http_client: &WrappedClient,
-                    data: &T,
-                    value: Option<&CommandOptionValue>,
-                    resolved: Option<&mut CommandInteractionDataResolved>
+                async fn parse_with(
+                    ctx: &mut ParseContext<'_, T>,
+                    value: Option<&CommandOptionValue>
                 ) -> Result<Self, ParseError>
                 {
-                    let num = usize::parse(http_client, data, value, resolved).await?;
+                    let num = usize::parse_with(ctx, value).await?;
                     match num {
                         #parse_stream
                         _ => return Err(ParseError::Parsing {
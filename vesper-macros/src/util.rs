@@ -1,7 +1,7 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
 use syn::spanned::Spanned;
-use syn::{parse2, Error, FnArg, GenericArgument, Pat, PatType, Path, PathArguments, Result, ReturnType, Signature, Type, Lifetime};
+use syn::{parse2, Block, Error, FnArg, GenericArgument, Pat, PatType, Path, PathArguments, Result, ReturnType, Signature, Type, Lifetime};
 use crate::util;
 
 /// Gets the path of the futurize macro
@@ -96,13 +96,56 @@ pub fn get_generic_arguments(path: &Path) -> Result<impl Iterator<Item = &Generi
     }
 }
 
+/// Gets the output and error types of the `CommandInfo` the given argument refers to, using the
+/// defaults of the framework when they are not specified.
+pub fn get_info_types(arg: &FnArg) -> Result<(Type, Type)> {
+    let mut types = get_generic_arguments(get_path(&get_pat(arg)?.ty, true)?)?
+        .filter_map(|generic| match generic {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None
+        })
+        // The first type is the one of the context.
+        .skip(1);
+
+    let output = match types.next() {
+        Some(ty) => ty,
+        None => parse2(quote::quote!(()))?
+    };
+    let error = match types.next() {
+        Some(ty) => ty,
+        None => parse2(quote::quote!(::vesper::framework::DefaultError))?
+    };
+
+    Ok((output, error))
+}
+
 pub fn get_return_type(sig: &Signature) -> Result<Box<Type>> {
     match &sig.output {
-        ReturnType::Default => return Err(Error::new(sig.output.span(), "Return type must be a Result<T, E>")),
+        ReturnType::Default => Err(Error::new(sig.output.span(), "Return type must be a Result<T, E>")),
         ReturnType::Type(_, kind) => Ok(kind.clone())
     }
 }
 
+/// Makes the function return its errors wrapped inside a `FrameworkError`, so the errors raised
+/// while parsing the arguments can be returned before the original block runs.
+pub fn wrap_framework_error(sig: &mut Signature, block: &mut Block, output: &Type) -> Result<()> {
+    let returnable = get_returnable_trait();
+    sig.output = parse2(quote::quote!(
+        -> ::std::result::Result<
+            <#output as #returnable>::Ok,
+            ::vesper::error::FrameworkError<<#output as #returnable>::Err>
+        >
+    ))?;
+
+    let b = &block;
+    *block = parse2(quote::quote! {{
+        let __output: #output = async move #b.await;
+        __output.map_err(::vesper::error::FrameworkError::User)
+    }})?;
+
+    Ok(())
+}
+
 pub fn get_context_type(sig: &Signature, allow_references: bool) -> Result<Type> {
     let arg = match sig.inputs.iter().next() {
         None => {
@@ -160,9 +203,9 @@ pub fn get_bracketed_generic<F>(arg: &FnArg, allow_references: bool, fun: F) ->
 where
     F: Fn(&Type) -> Result<Type>
 {
-    let mut generics = get_generic_arguments(get_path(&get_pat(arg)?.ty, allow_references)?)?;
+    let generics = get_generic_arguments(get_path(&get_pat(arg)?.ty, allow_references)?)?;
 
-    while let Some(next) = generics.next() {
+    for next in generics {
         match next {
             GenericArgument::Lifetime(_) => (),
             GenericArgument::Type(ty) => return Ok(Some(fun(ty)?)),
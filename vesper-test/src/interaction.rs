@@ -0,0 +1,594 @@
+use crate::{mock, APPLICATION_ID};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use twilight_model::{
+    application::{
+        command::CommandOptionType,
+        interaction::{Interaction, InteractionChannel, InteractionMember},
+    },
+    channel::{
+        message::{component::ComponentType, Message},
+        Attachment,
+    },
+    guild::{Permissions, Role},
+    id::{
+        marker::{
+            ApplicationMarker, AttachmentMarker, ChannelMarker, GenericMarker, GuildMarker,
+            MessageMarker, RoleMarker, UserMarker,
+        },
+        Id,
+    },
+    user::User,
+};
+
+/// Ids given to the built interactions, unique across the whole process so waiters never match
+/// interactions created by other tests.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1000);
+
+/// The value of a command option.
+#[derive(Clone, Debug)]
+pub enum OptionValue {
+    String(String),
+    Integer(i64),
+    Number(f64),
+    Boolean(bool),
+    User(Id<UserMarker>),
+    Channel(Id<ChannelMarker>),
+    Role(Id<RoleMarker>),
+    Mentionable(Id<GenericMarker>),
+    Attachment(Id<AttachmentMarker>),
+}
+
+impl OptionValue {
+    fn kind(&self) -> CommandOptionType {
+        match self {
+            Self::String(_) => CommandOptionType::String,
+            Self::Integer(_) => CommandOptionType::Integer,
+            Self::Number(_) => CommandOptionType::Number,
+            Self::Boolean(_) => CommandOptionType::Boolean,
+            Self::User(_) => CommandOptionType::User,
+            Self::Channel(_) => CommandOptionType::Channel,
+            Self::Role(_) => CommandOptionType::Role,
+            Self::Mentionable(_) => CommandOptionType::Mentionable,
+            Self::Attachment(_) => CommandOptionType::Attachment,
+        }
+    }
+
+    fn value(&self) -> Value {
+        match self {
+            Self::String(s) => json!(s),
+            Self::Integer(i) => json!(i),
+            Self::Number(n) => json!(n),
+            Self::Boolean(b) => json!(b),
+            Self::User(id) => json!(id),
+            Self::Channel(id) => json!(id),
+            Self::Role(id) => json!(id),
+            Self::Mentionable(id) => json!(id),
+            Self::Attachment(id) => json!(id),
+        }
+    }
+}
+
+macro_rules! option_value_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for OptionValue {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+option_value_from! {
+    &str => String,
+    String => String,
+    i64 => Integer,
+    f64 => Number,
+    bool => Boolean,
+    Id<UserMarker> => User,
+    Id<ChannelMarker> => Channel,
+    Id<RoleMarker> => Role,
+    Id<GenericMarker> => Mentionable,
+    Id<AttachmentMarker> => Attachment,
+}
+
+/// The kind of interaction being built.
+enum Kind {
+    Command {
+        kind: u8,
+        name: String,
+        target: Option<u64>,
+    },
+    Autocomplete {
+        name: String,
+    },
+    Component {
+        custom_id: String,
+        component_type: ComponentType,
+        values: Vec<String>,
+    },
+    ModalSubmit {
+        custom_id: String,
+    },
+}
+
+/// A builder of [interactions](Interaction) that can be given to `Framework::process`.
+///
+/// By default, interactions are created in direct messages by a user with id `2`, use
+/// [guild](Self::guild) to create them inside a guild.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::id::Id;
+/// use vesper_test::{mock, InteractionBuilder};
+///
+/// let target = Id::new(10);
+///
+/// let interaction = InteractionBuilder::chat("config")
+///     .subcommand("ban")
+///     .option("user", target)
+///     .option("reason", "Spam")
+///     .resolved_user(mock::user(target, "spammer"))
+///     .guild(Id::new(20))
+///     .build();
+/// ```
+pub struct InteractionBuilder {
+    kind: Kind,
+    id: u64,
+    application_id: Id<ApplicationMarker>,
+    token: String,
+    path: Vec<(String, CommandOptionType)>,
+    options: Vec<Value>,
+    resolved: Map<String, Value>,
+    fields: Vec<(String, String)>,
+    guild_id: Option<Id<GuildMarker>>,
+    channel_id: Id<ChannelMarker>,
+    author: Value,
+    author_id: Id<UserMarker>,
+    member_roles: Vec<Id<RoleMarker>>,
+    member_permissions: Permissions,
+    app_permissions: Option<Permissions>,
+    locale: String,
+    guild_locale: Option<String>,
+    message: Option<Value>,
+}
+
+impl InteractionBuilder {
+    fn new(kind: Kind) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let author_id = Id::new(2);
+
+        Self {
+            kind,
+            id,
+            application_id: APPLICATION_ID,
+            token: format!("token-{}", id),
+            path: Vec::new(),
+            options: Vec::new(),
+            resolved: Map::new(),
+            fields: Vec::new(),
+            guild_id: None,
+            channel_id: Id::new(3),
+            author: mock::user_value(author_id, "tester"),
+            author_id,
+            member_roles: Vec::new(),
+            member_permissions: Permissions::empty(),
+            app_permissions: None,
+            locale: String::from("en-US"),
+            guild_locale: None,
+            message: None,
+        }
+    }
+
+    /// Creates a chat input command interaction.
+    pub fn chat(name: impl Into<String>) -> Self {
+        Self::new(Kind::Command {
+            kind: 1,
+            name: name.into(),
+            target: None,
+        })
+    }
+
+    /// Creates a user context menu command interaction targeting the given user.
+    ///
+    /// If no user with the given id is [resolved](Self::resolved_user), a mocked one is used.
+    pub fn user(name: impl Into<String>, target: Id<UserMarker>) -> Self {
+        Self::new(Kind::Command {
+            kind: 2,
+            name: name.into(),
+            target: Some(target.get()),
+        })
+    }
+
+    /// Creates a message context menu command interaction targeting the given message.
+    ///
+    /// If no message with the given id is [resolved](Self::resolved_message), a mocked one is used.
+    pub fn message(name: impl Into<String>, target: Id<MessageMarker>) -> Self {
+        Self::new(Kind::Command {
+            kind: 3,
+            name: name.into(),
+            target: Some(target.get()),
+        })
+    }
+
+    /// Creates an autocomplete interaction for the given chat input command, the argument
+    /// being autocompleted must be set using [focused](Self::focused).
+    pub fn autocomplete(name: impl Into<String>) -> Self {
+        Self::new(Kind::Autocomplete { name: name.into() })
+    }
+
+    /// Creates a message component interaction of the given type.
+    pub fn component<I, V>(custom_id: impl Into<String>, component_type: ComponentType, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        Self::new(Kind::Component {
+            custom_id: custom_id.into(),
+            component_type,
+            values: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Creates a button click interaction.
+    pub fn button(custom_id: impl Into<String>) -> Self {
+        Self::component(custom_id, ComponentType::Button, Vec::<String>::new())
+    }
+
+    /// Creates a text select menu interaction with the given selected values.
+    pub fn select_menu<I, V>(custom_id: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        Self::component(custom_id, ComponentType::TextSelectMenu, values)
+    }
+
+    /// Creates a modal submit interaction, the submitted values must be set using
+    /// [field](Self::field).
+    pub fn modal_submit(custom_id: impl Into<String>) -> Self {
+        Self::new(Kind::ModalSubmit {
+            custom_id: custom_id.into(),
+        })
+    }
+
+    /// Sets the id of the interaction.
+    pub fn id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Sets the token of the interaction.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = token.into();
+        self
+    }
+
+    /// Sets the application id of the interaction, by default [APPLICATION_ID].
+    pub fn application_id(mut self, application_id: Id<ApplicationMarker>) -> Self {
+        self.application_id = application_id;
+        self
+    }
+
+    /// Invokes the given subcommand.
+    pub fn subcommand(mut self, name: impl Into<String>) -> Self {
+        self.path = vec![(name.into(), CommandOptionType::SubCommand)];
+        self
+    }
+
+    /// Invokes the given subcommand inside the given subcommand group.
+    pub fn subcommand_group(mut self, group: impl Into<String>, name: impl Into<String>) -> Self {
+        self.path = vec![
+            (group.into(), CommandOptionType::SubCommandGroup),
+            (name.into(), CommandOptionType::SubCommand),
+        ];
+        self
+    }
+
+    /// Adds an option to the invoked command.
+    pub fn option(mut self, name: impl Into<String>, value: impl Into<OptionValue>) -> Self {
+        let value = value.into();
+        self.options.push(json!({
+            "name": name.into(),
+            "type": value.kind(),
+            "value": value.value(),
+        }));
+        self
+    }
+
+    /// Adds the option being autocompleted, with the partial input of the user.
+    pub fn focused(mut self, name: impl Into<String>, input: impl Into<String>, kind: CommandOptionType) -> Self {
+        self.options.push(json!({
+            "name": name.into(),
+            "type": kind,
+            "value": input.into(),
+            "focused": true,
+        }));
+        self
+    }
+
+    fn resolve<V: Serialize>(&mut self, kind: &str, id: impl ToString, value: V) {
+        let value = serde_json::to_value(value).expect("Models are always serializable");
+        self.resolved
+            .entry(kind)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .unwrap()
+            .insert(id.to_string(), value);
+    }
+
+    /// Adds a user to the resolved data of the interaction.
+    pub fn resolved_user(mut self, user: User) -> Self {
+        let id = user.id;
+        self.resolve("users", id, user);
+        self
+    }
+
+    /// Adds the member of the given user to the resolved data of the interaction.
+    pub fn resolved_member(mut self, user_id: Id<UserMarker>, member: InteractionMember) -> Self {
+        self.resolve("members", user_id, member);
+        self
+    }
+
+    /// Adds a role to the resolved data of the interaction.
+    pub fn resolved_role(mut self, role: Role) -> Self {
+        let id = role.id;
+        self.resolve("roles", id, role);
+        self
+    }
+
+    /// Adds a channel to the resolved data of the interaction.
+    pub fn resolved_channel(mut self, channel: InteractionChannel) -> Self {
+        let id = channel.id;
+        self.resolve("channels", id, channel);
+        self
+    }
+
+    /// Adds an attachment to the resolved data of the interaction.
+    pub fn resolved_attachment(mut self, attachment: Attachment) -> Self {
+        let id = attachment.id;
+        self.resolve("attachments", id, attachment);
+        self
+    }
+
+    /// Adds a message to the resolved data of the interaction.
+    pub fn resolved_message(mut self, message: Message) -> Self {
+        let id = message.id;
+        self.resolve("messages", id, message);
+        self
+    }
+
+    /// Adds a submitted text input value to a modal submit interaction.
+    pub fn field(mut self, custom_id: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((custom_id.into(), value.into()));
+        self
+    }
+
+    /// Creates the interaction inside the given guild, invoked by a member of it.
+    pub fn guild(mut self, guild_id: Id<GuildMarker>) -> Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+
+    /// Sets the channel the interaction has been created in.
+    pub fn channel(mut self, channel_id: Id<ChannelMarker>) -> Self {
+        self.channel_id = channel_id;
+        self
+    }
+
+    /// Sets the user invoking the interaction.
+    pub fn author(mut self, user: User) -> Self {
+        self.author_id = user.id;
+        self.author = serde_json::to_value(user).expect("Users are always serializable");
+        self
+    }
+
+    /// Sets the roles of the member invoking the interaction, only used inside guilds.
+    pub fn member_roles(mut self, roles: Vec<Id<RoleMarker>>) -> Self {
+        self.member_roles = roles;
+        self
+    }
+
+    /// Sets the permissions of the member invoking the interaction, only used inside guilds.
+    pub fn member_permissions(mut self, permissions: Permissions) -> Self {
+        self.member_permissions = permissions;
+        self
+    }
+
+    /// Sets the permissions the application has in the channel of the interaction.
+    pub fn app_permissions(mut self, permissions: Permissions) -> Self {
+        self.app_permissions = Some(permissions);
+        self
+    }
+
+    /// Sets the locale of the user invoking the interaction, by default `en-US`.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = locale.into();
+        self
+    }
+
+    /// Sets the locale of the guild the interaction has been created in.
+    pub fn guild_locale(mut self, locale: impl Into<String>) -> Self {
+        self.guild_locale = Some(locale.into());
+        self
+    }
+
+    /// Sets the message a component interaction is attached to.
+    pub fn attached_message(mut self, message: Message) -> Self {
+        self.message = Some(serde_json::to_value(message).expect("Messages are always serializable"));
+        self
+    }
+
+    fn command_options(&mut self) -> Vec<Value> {
+        let mut options = std::mem::take(&mut self.options);
+
+        for (name, kind) in self.path.iter().rev() {
+            options = vec![json!({
+                "name": name,
+                "type": kind,
+                "options": options,
+            })];
+        }
+
+        options
+    }
+
+    fn data(&mut self) -> Value {
+        match &self.kind {
+            Kind::Command { kind, name, target } => {
+                let (kind, name, target) = (*kind, name.clone(), *target);
+
+                match (kind, target) {
+                    (2, Some(target)) if !self.has_resolved("users", target) => {
+                        self.resolve("users", target, mock::user_value(Id::new(target), "target"));
+                    }
+                    (3, Some(target)) if !self.has_resolved("messages", target) => {
+                        let author = mock::user_value(self.author_id, "tester");
+                        let message = mock::message_value(target, author, json!({}));
+                        self.resolve("messages", target, message);
+                    }
+                    _ => (),
+                }
+
+                let mut data = self.command_data(name, kind);
+                if let Some(target) = target {
+                    data.insert(String::from("target_id"), json!(target.to_string()));
+                }
+
+                Value::Object(data)
+            }
+            Kind::Autocomplete { name } => {
+                let name = name.clone();
+                Value::Object(self.command_data(name, 1))
+            }
+            Kind::Component {
+                custom_id,
+                component_type,
+                values,
+            } => json!({
+                "custom_id": custom_id,
+                "component_type": component_type,
+                "values": values,
+            }),
+            Kind::ModalSubmit { custom_id } => {
+                let rows = self
+                    .fields
+                    .iter()
+                    .map(|(custom_id, value)| {
+                        json!({
+                            "type": 1,
+                            "components": [{
+                                "type": 4,
+                                "custom_id": custom_id,
+                                "value": value,
+                            }],
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "custom_id": custom_id,
+                    "components": rows,
+                })
+            }
+        }
+    }
+
+    fn has_resolved(&self, kind: &str, id: u64) -> bool {
+        self.resolved
+            .get(kind)
+            .and_then(|map| map.get(id.to_string()))
+            .is_some()
+    }
+
+    fn command_data(&mut self, name: String, kind: u8) -> Map<String, Value> {
+        let mut data = Map::new();
+        data.insert(String::from("id"), json!(self.id.to_string()));
+        data.insert(String::from("name"), json!(name));
+        data.insert(String::from("type"), json!(kind));
+        data.insert(String::from("options"), Value::Array(self.command_options()));
+
+        if let Some(guild_id) = self.guild_id {
+            data.insert(String::from("guild_id"), json!(guild_id));
+        }
+
+        if !self.resolved.is_empty() {
+            data.insert(String::from("resolved"), Value::Object(self.resolved.clone()));
+        }
+
+        data
+    }
+
+    fn interaction_type(&self) -> u8 {
+        match self.kind {
+            Kind::Command { .. } => 2,
+            Kind::Component { .. } => 3,
+            Kind::Autocomplete { .. } => 4,
+            Kind::ModalSubmit { .. } => 5,
+        }
+    }
+
+    /// Builds the interaction.
+    pub fn build(mut self) -> Interaction {
+        let kind = self.interaction_type();
+        let data = self.data();
+
+        let mut interaction = Map::new();
+        interaction.insert(String::from("id"), json!(self.id.to_string()));
+        interaction.insert(String::from("application_id"), json!(self.application_id));
+        interaction.insert(String::from("type"), json!(kind));
+        interaction.insert(String::from("token"), json!(self.token));
+        interaction.insert(String::from("version"), json!(1));
+        interaction.insert(String::from("data"), data);
+        interaction.insert(String::from("channel_id"), json!(self.channel_id));
+        interaction.insert(String::from("locale"), json!(self.locale));
+        interaction.insert(String::from("entitlements"), json!([]));
+        interaction.insert(String::from("authorizing_integration_owners"), json!({}));
+
+        if let Some(guild_id) = self.guild_id {
+            interaction.insert(String::from("guild_id"), json!(guild_id));
+            interaction.insert(String::from("context"), json!(0));
+            interaction.insert(
+                String::from("channel"),
+                json!({ "id": self.channel_id, "type": 0 }),
+            );
+            interaction.insert(
+                String::from("member"),
+                json!({
+                    "user": self.author,
+                    "roles": self.member_roles,
+                    "permissions": self.member_permissions,
+                    "joined_at": mock::TIMESTAMP,
+                    "deaf": false,
+                    "mute": false,
+                    "flags": 0,
+                }),
+            );
+        } else {
+            interaction.insert(String::from("context"), json!(1));
+            interaction.insert(
+                String::from("channel"),
+                json!({ "id": self.channel_id, "type": 1 }),
+            );
+            interaction.insert(String::from("user"), self.author);
+        }
+
+        if let Some(locale) = self.guild_locale {
+            interaction.insert(String::from("guild_locale"), json!(locale));
+        }
+
+        if let Some(permissions) = self.app_permissions {
+            interaction.insert(String::from("app_permissions"), json!(permissions));
+        }
+
+        if let Some(message) = self.message {
+            interaction.insert(String::from("message"), message);
+        }
+
+        mock::model(Value::Object(interaction))
+    }
+}
@@ -0,0 +1,14 @@
+#![doc = include_str!("../README.md")]
+
+pub mod interaction;
+pub mod mock;
+pub mod recorder;
+
+pub use interaction::{InteractionBuilder, OptionValue};
+pub use recorder::{RecordedCall, RecordedRequest, Recorder};
+
+use twilight_model::id::{marker::ApplicationMarker, Id};
+
+/// The application id used by default in the interactions created by the
+/// [builder](InteractionBuilder) and in the messages returned by the [recorder](Recorder).
+pub const APPLICATION_ID: Id<ApplicationMarker> = Id::new(1);
@@ -0,0 +1,121 @@
+use serde_json::{json, Value};
+use twilight_model::{
+    application::interaction::{InteractionChannel, InteractionMember},
+    channel::{message::Message, Attachment, ChannelType},
+    guild::{Permissions, Role},
+    id::{
+        marker::{AttachmentMarker, ChannelMarker, MessageMarker, RoleMarker, UserMarker},
+        Id,
+    },
+    user::User,
+};
+
+/// The timestamp used in every mocked object requiring one.
+pub(crate) const TIMESTAMP: &str = "2024-01-01T00:00:00.000000+00:00";
+
+/// Deserializes the given value into a model, panicking if the value is not valid.
+pub(crate) fn model<T: serde::de::DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("Mocked objects must be valid models")
+}
+
+/// Creates a user with the given id and name.
+pub fn user(id: Id<UserMarker>, name: &str) -> User {
+    model(user_value(id, name))
+}
+
+pub(crate) fn user_value(id: Id<UserMarker>, name: &str) -> Value {
+    json!({
+        "id": id,
+        "username": name,
+        "discriminator": "0000",
+        "avatar": null,
+        "bot": false,
+    })
+}
+
+/// Creates a guild member, as found in resolved data, with the given roles and permissions.
+pub fn member(roles: Vec<Id<RoleMarker>>, permissions: Permissions) -> InteractionMember {
+    model(json!({
+        "roles": roles,
+        "permissions": permissions,
+        "joined_at": TIMESTAMP,
+        "nick": null,
+        "pending": false,
+        "flags": 0,
+    }))
+}
+
+/// Creates a role with the given id, name and permissions.
+pub fn role(id: Id<RoleMarker>, name: &str, permissions: Permissions) -> Role {
+    model(json!({
+        "id": id,
+        "name": name,
+        "permissions": permissions,
+        "color": 0,
+        "hoist": false,
+        "managed": false,
+        "mentionable": true,
+        "position": 1,
+        "flags": 0,
+    }))
+}
+
+/// Creates a channel, as found in resolved data, with the given id, name and type.
+pub fn channel(id: Id<ChannelMarker>, name: &str, kind: ChannelType) -> InteractionChannel {
+    model(json!({
+        "id": id,
+        "name": name,
+        "type": kind,
+        "permissions": Permissions::all(),
+    }))
+}
+
+/// Creates an attachment with the given id and file name.
+pub fn attachment(id: Id<AttachmentMarker>, filename: &str) -> Attachment {
+    model(json!({
+        "id": id,
+        "filename": filename,
+        "size": 0,
+        "url": format!("https://cdn.discordapp.com/attachments/{}/{}", id, filename),
+        "proxy_url": format!("https://media.discordapp.net/attachments/{}/{}", id, filename),
+    }))
+}
+
+/// Creates a message with the given id and content, sent by the given author.
+pub fn message(id: Id<MessageMarker>, author: &User, content: &str) -> Message {
+    model(message_value(
+        id.get(),
+        serde_json::to_value(author).expect("Users are always serializable"),
+        json!({ "content": content }),
+    ))
+}
+
+/// Creates the value of a message sent by the given author, taking the content, embeds,
+/// components and flags from the given request body.
+pub(crate) fn message_value(id: u64, author: Value, body: Value) -> Value {
+    let field = |name: &str, default: Value| {
+        body.get(name)
+            .filter(|value| !value.is_null())
+            .cloned()
+            .unwrap_or(default)
+    };
+
+    json!({
+        "id": id.to_string(),
+        "channel_id": "1",
+        "author": author,
+        "content": field("content", json!("")),
+        "embeds": field("embeds", json!([])),
+        "components": field("components", json!([])),
+        "flags": field("flags", json!(0)),
+        "attachments": [],
+        "mentions": [],
+        "mention_roles": [],
+        "mention_everyone": false,
+        "pinned": false,
+        "tts": false,
+        "type": 0,
+        "timestamp": TIMESTAMP,
+        "edited_timestamp": null,
+    })
+}
@@ -0,0 +1,307 @@
+use crate::{mock, APPLICATION_ID};
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, Method, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper::{body::Incoming, server::conn::http1, service::service_fn};
+use hyper_util::rt::TokioIo;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+use twilight_http::Client;
+use twilight_model::http::interaction::InteractionResponse;
+
+/// A request received by the [recorder](Recorder).
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    /// The method of the request.
+    pub method: Method,
+    /// The path of the request, without the api version prefix nor the query.
+    pub path: String,
+    /// The raw body of the request.
+    pub body: Bytes,
+}
+
+impl RecordedRequest {
+    /// Parses the body of the request as json, returning [`Value::Null`] if it is not valid json,
+    /// this is, when the request contains attachments.
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+}
+
+/// A call made to discord's http api, as recorded by the [recorder](Recorder).
+#[non_exhaustive]
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum RecordedCall {
+    /// The initial response of an interaction was created.
+    InitialResponse {
+        interaction_id: String,
+        token: String,
+        response: InteractionResponse,
+    },
+    /// The initial response of an interaction was edited.
+    EditResponse { token: String, body: Value },
+    /// The initial response of an interaction was deleted.
+    DeleteResponse { token: String },
+    /// A followup message was created.
+    Followup { token: String, body: Value },
+    /// A followup message was edited.
+    EditFollowup {
+        token: String,
+        message_id: String,
+        body: Value,
+    },
+    /// A followup message was deleted.
+    DeleteFollowup { token: String, message_id: String },
+    /// Any other request.
+    Other(RecordedRequest),
+}
+
+impl RecordedCall {
+    fn new(request: RecordedRequest) -> Self {
+        let segments = request.path.split('/').collect::<Vec<_>>();
+        let method = request.method.clone();
+
+        match (method.as_str(), segments.as_slice()) {
+            ("POST", ["interactions", id, token, "callback"]) => {
+                match serde_json::from_slice(&request.body) {
+                    Ok(response) => Self::InitialResponse {
+                        interaction_id: id.to_string(),
+                        token: token.to_string(),
+                        response,
+                    },
+                    Err(_) => Self::Other(request),
+                }
+            }
+            ("PATCH", ["webhooks", _, token, "messages", "@original"]) => Self::EditResponse {
+                token: token.to_string(),
+                body: request.json(),
+            },
+            ("DELETE", ["webhooks", _, token, "messages", "@original"]) => Self::DeleteResponse {
+                token: token.to_string(),
+            },
+            ("POST", ["webhooks", _, token]) => Self::Followup {
+                token: token.to_string(),
+                body: request.json(),
+            },
+            ("PATCH", ["webhooks", _, token, "messages", id]) => Self::EditFollowup {
+                token: token.to_string(),
+                message_id: id.to_string(),
+                body: request.json(),
+            },
+            ("DELETE", ["webhooks", _, token, "messages", id]) => Self::DeleteFollowup {
+                token: token.to_string(),
+                message_id: id.to_string(),
+            },
+            _ => Self::Other(request),
+        }
+    }
+}
+
+/// A response queued to be returned by the recorder.
+struct QueuedResponse {
+    status: StatusCode,
+    body: Value,
+}
+
+#[derive(Default)]
+struct State {
+    calls: Mutex<Vec<RecordedCall>>,
+    queued: Mutex<VecDeque<QueuedResponse>>,
+    next_message_id: AtomicU64,
+}
+
+impl State {
+    /// The response returned when no response is queued.
+    fn default_response(&self, call: &RecordedCall) -> (StatusCode, Option<Value>) {
+        match call {
+            RecordedCall::Followup { body, .. }
+            | RecordedCall::EditResponse { body, .. }
+            | RecordedCall::EditFollowup { body, .. } => {
+                let id = self.next_message_id.fetch_add(1, Ordering::Relaxed) + 1;
+                let author = mock::user_value(APPLICATION_ID.cast(), "vesper-test");
+
+                (StatusCode::OK, Some(mock::message_value(id, author, body.clone())))
+            }
+            _ => (StatusCode::NO_CONTENT, None),
+        }
+    }
+}
+
+/// A fake transport for the http client, recording every call made to discord's api.
+///
+/// The recorder starts a local http server, and the [client](Self::client) it provides sends
+/// all requests to it instead of discord. By default, the initial responses and deletions are
+/// answered with `204 No Content`, and the creation and edition of messages returns a message
+/// containing the sent content, embeds and components. Any other request is answered with
+/// `204 No Content` unless a response is [queued](Self::respond_with).
+///
+/// The server is stopped when the recorder is dropped.
+pub struct Recorder {
+    address: SocketAddr,
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl Recorder {
+    /// Starts a new recorder listening in a random local port.
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind the recorder");
+        let address = listener.local_addr().expect("Failed to get the recorder address");
+        let state = Arc::new(State::default());
+
+        let task = tokio::spawn(accept(listener, Arc::clone(&state)));
+
+        Self {
+            address,
+            state,
+            task,
+        }
+    }
+
+    /// Returns a new http client sending all the requests to this recorder.
+    pub fn client(&self) -> Client {
+        Client::builder()
+            .proxy(self.address.to_string(), true)
+            .ratelimiter(None)
+            .token(String::from("vesper-test"))
+            .build()
+    }
+
+    /// Returns the address the recorder is listening on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Queues a response that will be returned to the next request, instead of the default one.
+    pub fn respond_with(&self, status: u16, body: Value) {
+        self.state.queued.lock().push_back(QueuedResponse {
+            status: StatusCode::from_u16(status).expect("Invalid status code"),
+            body,
+        });
+    }
+
+    /// Returns all the calls recorded, in the order they were made.
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.state.calls.lock().clone()
+    }
+
+    /// Returns and removes all the calls recorded.
+    pub fn take_calls(&self) -> Vec<RecordedCall> {
+        std::mem::take(&mut *self.state.calls.lock())
+    }
+
+    /// Clears all the recorded calls and the queued responses.
+    pub fn clear(&self) {
+        self.state.calls.lock().clear();
+        self.state.queued.lock().clear();
+    }
+
+    /// Returns the first initial response recorded.
+    pub fn initial_response(&self) -> Option<InteractionResponse> {
+        self.state.calls.lock().iter().find_map(|call| match call {
+            RecordedCall::InitialResponse { response, .. } => Some(response.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns the bodies of all the followup messages recorded.
+    pub fn followups(&self) -> Vec<Value> {
+        self.state
+            .calls
+            .lock()
+            .iter()
+            .filter_map(|call| match call {
+                RecordedCall::Followup { body, .. } => Some(body.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the bodies of all the edits of the initial response recorded.
+    pub fn edits(&self) -> Vec<Value> {
+        self.state
+            .calls
+            .lock()
+            .iter()
+            .filter_map(|call| match call {
+                RecordedCall::EditResponse { body, .. } => Some(body.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn accept(listener: TcpListener, state: Arc<State>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let state = Arc::clone(&state);
+
+        tokio::spawn(async move {
+            let service = service_fn(move |request| handle(Arc::clone(&state), request));
+
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+async fn handle(state: Arc<State>, request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let method = request.method().clone();
+    let path = strip_prefix(request.uri().path());
+    let body = request
+        .into_body()
+        .collect()
+        .await
+        .map(|body| body.to_bytes())
+        .unwrap_or_default();
+
+    let call = RecordedCall::new(RecordedRequest { method, path, body });
+    let queued = state.queued.lock().pop_front();
+    let (status, body) = match queued {
+        Some(queued) => (queued.status, Some(queued.body)),
+        None => state.default_response(&call),
+    };
+
+    state.calls.lock().push(call);
+
+    let mut response = Response::builder().status(status);
+    let body = match body {
+        Some(body) => {
+            response = response.header(CONTENT_TYPE, "application/json");
+            Bytes::from(serde_json::to_vec(&body).expect("Json values are always serializable"))
+        }
+        None => Bytes::new(),
+    };
+
+    Ok(response.body(Full::new(body)).expect("Responses are always valid"))
+}
+
+/// Removes the `/api/v{version}/` prefix of the given path.
+fn strip_prefix(path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    let path = path.strip_prefix("api/").unwrap_or(path);
+
+    match path.split_once('/') {
+        Some((version, rest)) if version.starts_with('v') => rest.to_string(),
+        _ => path.to_string(),
+    }
+}
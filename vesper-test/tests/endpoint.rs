@@ -0,0 +1,150 @@
+use ed25519_dalek::{Signer, SigningKey};
+use http::{Request, Response, StatusCode};
+use rand::rngs::OsRng;
+use serde_json::Value;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use vesper::endpoint::{InteractionEndpoint, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use vesper::prelude::*;
+use vesper::twilight_exports::{
+    Interaction, InteractionResponse, InteractionResponseData, InteractionResponseType, InteractionType,
+};
+use vesper_test::{InteractionBuilder, RecordedCall, Recorder, APPLICATION_ID};
+
+#[command]
+#[description = "Says hello"]
+async fn hello(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    ctx.reply("Hello!").await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Takes too long to answer"]
+async fn slow(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    ctx.create_response(&InteractionResponse {
+        kind: InteractionResponseType::ChannelMessageWithSource,
+        data: Some(InteractionResponseData {
+            content: Some(String::from("Finally")),
+            ..Default::default()
+        }),
+    })
+    .await?;
+    ctx.reply("And a followup").await?;
+    Ok(())
+}
+
+struct Harness {
+    recorder: Recorder,
+    endpoint: InteractionEndpoint<()>,
+    key: SigningKey,
+}
+
+impl Harness {
+    async fn new() -> Self {
+        let recorder = Recorder::start().await;
+        let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+            .command(hello)
+            .command(slow)
+            .build();
+
+        let key = SigningKey::generate(&mut OsRng);
+        let endpoint = InteractionEndpoint::new(Arc::new(framework), &hex::encode(key.verifying_key().to_bytes()))
+            .unwrap()
+            .response_timeout(Duration::from_millis(50));
+
+        Self { recorder, endpoint, key }
+    }
+
+    fn request(&self, interaction: &Interaction, key: &SigningKey) -> Request<Vec<u8>> {
+        self.request_at(interaction, key, SystemTime::now())
+    }
+
+    fn request_at(&self, interaction: &Interaction, key: &SigningKey, time: SystemTime) -> Request<Vec<u8>> {
+        let timestamp = time.duration_since(UNIX_EPOCH).unwrap().as_secs().to_string();
+        let body = serde_json::to_vec(interaction).unwrap();
+        let mut message = timestamp.as_bytes().to_vec();
+        message.extend_from_slice(&body);
+
+        Request::post("/interactions")
+            .header(SIGNATURE_HEADER, hex::encode(key.sign(&message).to_bytes()))
+            .header(TIMESTAMP_HEADER, timestamp)
+            .body(body)
+            .unwrap()
+    }
+
+    async fn send(&self, interaction: &Interaction) -> Response<Vec<u8>> {
+        self.endpoint.handle(self.request(interaction, &self.key)).await
+    }
+}
+
+fn json(response: &Response<Vec<u8>>) -> Value {
+    serde_json::from_slice(response.body()).unwrap()
+}
+
+#[tokio::test]
+async fn answers_pings() {
+    let harness = Harness::new().await;
+    let mut ping = InteractionBuilder::chat("hello").build();
+    ping.kind = InteractionType::Ping;
+    ping.data = None;
+
+    let response = harness.send(&ping).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json(&response)["type"], 1);
+}
+
+#[tokio::test]
+async fn rejects_invalid_signatures() {
+    let harness = Harness::new().await;
+    let other = SigningKey::generate(&mut OsRng);
+    let request = harness.request(&InteractionBuilder::chat("hello").build(), &other);
+
+    let response = harness.endpoint.handle(request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(harness.recorder.calls().is_empty());
+}
+
+#[tokio::test]
+async fn rejects_stale_timestamps() {
+    let harness = Harness::new().await;
+    let sent_at = SystemTime::now() - Duration::from_secs(10 * 60);
+    let request = harness.request_at(&InteractionBuilder::chat("hello").build(), &harness.key, sent_at);
+
+    let response = harness.endpoint.handle(request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(harness.recorder.calls().is_empty());
+}
+
+#[tokio::test]
+async fn sends_the_initial_response_in_the_body() {
+    let harness = Harness::new().await;
+
+    let response = harness.send(&InteractionBuilder::chat("hello").build()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json(&response);
+    assert_eq!(body["type"], 4);
+    assert_eq!(body["data"]["content"], "Hello!");
+    assert!(harness.recorder.initial_response().is_none());
+}
+
+#[tokio::test]
+async fn edits_the_response_deferred_by_the_endpoint() {
+    let harness = Harness::new().await;
+
+    let response = harness.send(&InteractionBuilder::chat("slow").build()).await;
+    assert_eq!(json(&response)["type"], 5);
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let calls = harness.recorder.calls();
+    assert!(!calls.iter().any(|call| matches!(call, RecordedCall::InitialResponse { .. })));
+    assert_eq!(harness.recorder.edits()[0]["content"], "Finally");
+    assert_eq!(harness.recorder.followups()[0]["content"], "And a followup");
+}
@@ -0,0 +1,538 @@
+use vesper::framework::{DefaultError, ProcessResult};
+use vesper::parsers::Member;
+use vesper::prelude::*;
+use vesper::custom_id::CustomIdCodec;
+use vesper::checks::{guild_only, owner_only, owner_only_cached_for};
+use vesper::command::{ExecutionResult, ExecutionState};
+use vesper::middleware::{Layer, Next};
+use vesper::defer::Defer;
+use vesper::error_response::default_error_response;
+use vesper::twilight_exports::{Id, InteractionResponseType, Permissions};
+use serde_json::json;
+use std::time::Duration;
+use vesper_test::{mock, InteractionBuilder, RecordedCall, Recorder, APPLICATION_ID};
+
+#[command]
+#[description = "Repeats something"]
+async fn repeat(
+    ctx: &mut SlashContext<()>,
+    #[description = "The content"] content: String,
+    #[description = "How many times"] times: Option<i64>
+) -> DefaultCommandResult {
+    ctx.reply(content.repeat(times.unwrap_or(1) as usize)).await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Answers later"]
+async fn later(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    ctx.defer(false).await?;
+    ctx.edit("Done").await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Edits the deferred response using the interaction client"]
+async fn raw_edit(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    ctx.defer(false).await?;
+    ctx.interaction_client
+        .update_response(&ctx.interaction.token)
+        .content(Some("Edited"))
+        .await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Replies while the interaction is being deferred"]
+async fn racing(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    let deferrer = ctx.clone();
+    let (deferred, replied) = tokio::join!(deferrer.defer(false), ctx.reply("Raced"));
+    deferred?;
+    replied?;
+    Ok(())
+}
+
+#[command]
+#[description = "Never answers"]
+async fn silent(_ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    Ok(())
+}
+
+#[command]
+#[description = "Only works in guilds, once a minute"]
+#[checks(guild_only)]
+#[cooldown(global, uses = 1, seconds = 60)]
+async fn limited(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    ctx.reply("Done").await?;
+    Ok(())
+}
+
+#[derive(CustomId)]
+struct Refresh;
+
+#[component(state)]
+async fn refresh(ctx: &mut SlashContext<()>, _state: Refresh) -> DefaultCommandResult {
+    ctx.reply("Refreshed").await?;
+    Ok(())
+}
+
+#[component("explode")]
+async fn explode(_ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    panic!("Boom")
+}
+
+#[component("hang")]
+async fn hang(_ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    Ok(())
+}
+
+#[check]
+async fn exploding_check(_ctx: &mut SlashContext<()>) -> Result<bool, DefaultError> {
+    panic!("Boom")
+}
+
+#[command]
+#[description = "Has a check that panics"]
+#[checks(exploding_check)]
+async fn guarded(_ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    Ok(())
+}
+
+#[after]
+async fn exploding_after(
+    _ctx: &mut SlashContext<()>,
+    _info: &CommandInfo<()>,
+    _result: Option<DefaultFrameworkResult>
+) {
+    panic!("Boom")
+}
+
+#[command]
+#[description = "Always fails"]
+async fn failing(_ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    Err("Failed".into())
+}
+
+#[command(user, name = "Inspect")]
+#[description = "Shows the nickname of a member"]
+async fn inspect(ctx: &mut SlashContext<()>, target: Member) -> DefaultCommandResult {
+    ctx.reply(target.member.nick.unwrap_or_default()).await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Only for the owners"]
+#[checks(owner_only)]
+async fn shutdown(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    ctx.reply("Bye").await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Only for the owners, checked every time"]
+#[checks(owner_only_cached_for(Duration::ZERO))]
+async fn restart(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    ctx.reply("Restarting").await?;
+    Ok(())
+}
+
+struct Attempt(u8);
+
+struct Retry;
+
+#[async_trait]
+impl Layer<(), (), DefaultError> for Retry {
+    async fn call(
+        &self,
+        ctx: &mut SlashContext<'_, ()>,
+        next: Next<'_, (), (), DefaultError>
+    ) -> ExecutionResult<(), DefaultError> {
+        ctx.extensions.insert(Attempt(1));
+        let result = next.run(ctx).await;
+        if !matches!(result.state, ExecutionState::CommandErrored) {
+            return result;
+        }
+
+        ctx.extensions.insert(Attempt(2));
+        next.run(ctx).await
+    }
+}
+
+#[command]
+#[description = "Fails on the first attempt"]
+#[cooldown(global, uses = 1, seconds = 60)]
+#[layers(Retry)]
+async fn flaky(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+    match ctx.extensions.get::<Attempt>() {
+        Some(Attempt(2)) => {
+            ctx.reply("Second attempt").await?;
+            Ok(())
+        }
+        _ => Err("First attempt".into()),
+    }
+}
+
+type Seen = std::sync::Mutex<Vec<String>>;
+
+#[command]
+#[description = "Counts up to a number"]
+async fn count(
+    _ctx: &mut SlashContext<Seen>,
+    #[description = "The label"] label: String,
+    #[description = "Up to"] up_to: Range<i64, 1, 10>,
+    #[description = "The step"] step: Option<i64>
+) -> DefaultCommandResult {
+    let _ = (label, up_to, step);
+    Ok(())
+}
+
+#[after]
+async fn see_arguments(
+    ctx: &mut SlashContext<Seen>,
+    info: &CommandInfo<Seen>,
+    _result: Option<DefaultFrameworkResult>
+) {
+    let mut seen = ctx.data.lock().unwrap();
+    seen.extend(info.arguments().iter().map(|argument| format!("{}={:?}", argument.name, argument.value)));
+
+    if let Some(label) = info.argument("label").and_then(|argument| argument.downcast_ref::<String>()) {
+        seen.push(label.clone());
+    }
+}
+
+#[tokio::test]
+async fn records_the_initial_response() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(repeat)
+        .build();
+
+    let interaction = InteractionBuilder::chat("repeat")
+        .option("content", "ab")
+        .option("times", 2)
+        .build();
+
+    let result = framework.process(interaction).await;
+    assert!(matches!(result, ProcessResult::CommandExecuted(_)));
+
+    let response = recorder.initial_response().unwrap();
+    assert_eq!(response.kind, InteractionResponseType::ChannelMessageWithSource);
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("abab"));
+}
+
+#[tokio::test]
+async fn records_the_parsed_arguments() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, Seen::default())
+        .command(count)
+        .after(see_arguments)
+        .build();
+
+    let interaction = InteractionBuilder::chat("count")
+        .option("label", "sheep")
+        .option("up_to", 3)
+        .build();
+
+    framework.process(interaction).await;
+
+    assert_eq!(
+        *framework.data.lock().unwrap(),
+        ["label=\"sheep\"", "up_to=Range<i64, 1, 10>(3)", "step=None", "sheep"]
+    );
+}
+
+#[tokio::test]
+async fn caches_the_owners_for_their_ttl() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(shutdown)
+        .command(restart)
+        .build();
+
+    let application = json!({
+        "bot_public": true,
+        "bot_require_code_grant": false,
+        "description": "",
+        "flags": null,
+        "icon": null,
+        "id": APPLICATION_ID.to_string(),
+        "name": "vesper-test",
+        "owner": mock::user(Id::new(2), "tester"),
+        "team": null,
+        "verify_key": ""
+    });
+    let fetches = |recorder: &Recorder| {
+        recorder
+            .take_calls()
+            .into_iter()
+            .filter(|call| matches!(call, RecordedCall::Other(request) if request.path == "applications/@me"))
+            .count()
+    };
+
+    let state = |result| match result {
+        ProcessResult::CommandExecuted(result) => result.state,
+        _ => panic!("The command was not executed"),
+    };
+
+    recorder.respond_with(200, application.clone());
+    for _ in 0..2 {
+        let result = framework.process(InteractionBuilder::chat("shutdown").build()).await;
+        assert!(matches!(state(result), ExecutionState::CommandFinished));
+    }
+    assert_eq!(fetches(&recorder), 1);
+
+    for _ in 0..2 {
+        recorder.respond_with(200, application.clone());
+        let result = framework.process(InteractionBuilder::chat("restart").build()).await;
+        assert!(matches!(state(result), ExecutionState::CommandFinished));
+    }
+    assert_eq!(fetches(&recorder), 2);
+}
+
+#[tokio::test]
+async fn layers_can_retry_the_command() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(flaky)
+        .build();
+
+    let result = framework.process(InteractionBuilder::chat("flaky").build()).await;
+    let ProcessResult::CommandExecuted(result) = result else {
+        panic!("The command was not executed");
+    };
+
+    // The cooldown only applies once, so the retry is not rejected by it.
+    assert!(matches!(result.state, ExecutionState::CommandFinished));
+    let response = recorder.initial_response().unwrap();
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("Second attempt"));
+}
+
+#[tokio::test]
+async fn records_deferred_edits() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(later)
+        .build();
+
+    framework.process(InteractionBuilder::chat("later").build()).await;
+
+    let calls = recorder.calls();
+    assert!(matches!(
+        &calls[0],
+        RecordedCall::InitialResponse { response, .. }
+            if response.kind == InteractionResponseType::DeferredChannelMessageWithSource
+    ));
+    assert_eq!(recorder.edits()[0]["content"], "Done");
+    assert_eq!(calls.len(), 2);
+}
+
+#[tokio::test]
+async fn resolves_user_command_targets() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(inspect)
+        .build();
+
+    let target = Id::new(20);
+    let mut member = mock::member(Vec::new(), Permissions::empty());
+    member.nick = Some(String::from("Nick"));
+
+    let interaction = InteractionBuilder::user("Inspect", target)
+        .guild(Id::new(30))
+        .resolved_member(target, member)
+        .build();
+
+    framework.process(interaction).await;
+
+    let response = recorder.initial_response().unwrap();
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("Nick"));
+}
+
+#[tokio::test]
+async fn keeps_responses_edited_through_the_client() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .auto_defer(Defer::immediate())
+        .command(raw_edit)
+        .build();
+
+    framework.process(InteractionBuilder::chat("raw_edit").build()).await;
+
+    let edits = recorder.edits();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0]["content"], "Edited");
+}
+
+#[tokio::test]
+async fn sends_the_fallback_when_unanswered() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .auto_defer(Defer::before_deadline())
+        .fallback_response("Nothing to say")
+        .command(silent)
+        .build();
+
+    framework.process(InteractionBuilder::chat("silent").build()).await;
+
+    let response = recorder.initial_response().unwrap();
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("Nothing to say"));
+}
+
+#[tokio::test]
+async fn keeps_deferred_responses() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .auto_defer(Defer::immediate())
+        .fallback_response("Nothing to say")
+        .command(silent)
+        .build();
+
+    framework.process(InteractionBuilder::chat("silent").build()).await;
+
+    assert!(recorder.edits().is_empty());
+    assert_eq!(recorder.calls().len(), 1);
+}
+
+#[tokio::test]
+async fn serializes_concurrent_responses() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(racing)
+        .build();
+
+    framework.process(InteractionBuilder::chat("racing").build()).await;
+
+    let calls = recorder.calls();
+    assert!(matches!(
+        &calls[0],
+        RecordedCall::InitialResponse { response, .. }
+            if response.kind == InteractionResponseType::DeferredChannelMessageWithSource
+    ));
+    assert_eq!(recorder.edits()[0]["content"], "Raced");
+    assert_eq!(calls.len(), 2);
+}
+
+#[tokio::test]
+async fn routes_states_without_fields() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .component(refresh)
+        .build();
+
+    let custom_id = CustomIdCodec::new().encode(&Refresh).unwrap();
+    let result = framework.process(InteractionBuilder::button(custom_id).build()).await;
+
+    assert!(matches!(result, ProcessResult::ComponentHandled(_)));
+    let response = recorder.initial_response().unwrap();
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("Refreshed"));
+}
+
+#[tokio::test]
+async fn denied_invocations_keep_the_cooldown() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(limited)
+        .build();
+
+    let state = |result| match result {
+        ProcessResult::CommandExecuted(result) => result.state,
+        _ => panic!("The command was not executed"),
+    };
+
+    let denied = framework.process(InteractionBuilder::chat("limited").build()).await;
+    assert!(matches!(state(denied), ExecutionState::CheckFailed));
+
+    let guild = || InteractionBuilder::chat("limited").guild(Id::new(30)).build();
+    assert!(matches!(state(framework.process(guild()).await), ExecutionState::CommandFinished));
+
+    let ProcessResult::CommandExecuted(result) = framework.process(guild()).await else {
+        panic!("The command was not executed");
+    };
+    assert!(matches!(result.state, ExecutionState::OnCooldown(_)));
+    assert!(matches!(result.denial, Some(Denial::Cooldown(_))));
+}
+
+#[tokio::test]
+async fn cooldowns_use_the_denial_responder() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .denial_responder(|_, denial| match denial {
+            Denial::Cooldown(_) => Some(Reply::from("Slow down").ephemeral()),
+            _ => None,
+        })
+        .command(limited)
+        .build();
+
+    let guild = || InteractionBuilder::chat("limited").guild(Id::new(30)).build();
+    framework.process(guild()).await;
+    recorder.clear();
+
+    framework.process(guild()).await;
+    let response = recorder.initial_response().unwrap();
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("Slow down"));
+}
+
+#[tokio::test]
+async fn isolates_component_handlers() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command_timeout(Duration::from_millis(50))
+        .component(explode)
+        .component(hang)
+        .build();
+
+    let state = |result| match result {
+        ProcessResult::ComponentHandled(result) => result.state,
+        _ => panic!("The component was not handled"),
+    };
+
+    let panicked = framework.process(InteractionBuilder::button("explode").build()).await;
+    assert!(matches!(state(panicked), ExecutionState::Panicked));
+
+    let timed_out = framework.process(InteractionBuilder::button("hang").build()).await;
+    assert!(matches!(state(timed_out), ExecutionState::TimedOut(_)));
+}
+
+#[tokio::test]
+async fn isolates_checks_and_hooks() {
+    let recorder = Recorder::start().await;
+    let checked = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(guarded)
+        .build();
+    let hooked = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(silent)
+        .after(exploding_after)
+        .build();
+
+    let state = |result| match result {
+        ProcessResult::CommandExecuted(result) => result.state,
+        _ => panic!("The command was not executed"),
+    };
+
+    let guarded = checked.process(InteractionBuilder::chat("guarded").build()).await;
+    assert!(matches!(state(guarded), ExecutionState::Panicked));
+
+    let silent = hooked.process(InteractionBuilder::chat("silent").build()).await;
+    assert!(matches!(state(silent), ExecutionState::Panicked));
+}
+
+#[tokio::test]
+async fn error_responses_are_opt_in() {
+    let recorder = Recorder::start().await;
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(failing)
+        .build();
+
+    framework.process(InteractionBuilder::chat("failing").build()).await;
+    assert!(recorder.calls().is_empty());
+
+    let framework = Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(failing)
+        .error_responder(default_error_response)
+        .build();
+
+    framework.process(InteractionBuilder::chat("failing").build()).await;
+    let response = recorder.initial_response().unwrap();
+    assert!(response.data.unwrap().content.unwrap().contains("Error id"));
+}
@@ -0,0 +1,307 @@
+use vesper::command::OutputLocation;
+use vesper::error::FrameworkError;
+use vesper::framework::{DefaultError, ProcessResult};
+use vesper::parse::ParseError;
+use vesper::parsers::{MaybeMember, Member, Mentionable};
+use vesper::string::{Length, Validated, Validator};
+use vesper::prelude::*;
+use vesper::twilight_exports::{GenericMarker, Id, InteractionData, Permissions};
+use twilight_model::application::interaction::InteractionMember;
+use vesper_test::{mock, InteractionBuilder, Recorder, APPLICATION_ID};
+
+#[command]
+#[description = "Shows the nickname of a member"]
+async fn nickname(
+    ctx: &mut SlashContext<()>,
+    #[description = "The member"] member: Member
+) -> DefaultCommandResult {
+    ctx.reply(member.member.nick.unwrap_or_default()).await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Tells whether a user is a member"]
+async fn whois(
+    ctx: &mut SlashContext<()>,
+    #[description = "The user"] user: MaybeMember
+) -> DefaultCommandResult {
+    let reply = match user.member {
+        Some(_) => format!("{} is a member", user.user.name),
+        None => format!("{} is not a member", user.user.name),
+    };
+
+    ctx.reply(reply).await?;
+    Ok(())
+}
+
+#[command(user, name = "Nickname")]
+#[description = "Shows the nickname of a member"]
+async fn nickname_target(ctx: &mut SlashContext<()>, target: Member) -> DefaultCommandResult {
+    ctx.reply(target.member.nick.unwrap_or_default()).await?;
+    Ok(())
+}
+
+#[command]
+#[description = "Mentions a user or a role"]
+async fn mention(
+    ctx: &mut SlashContext<()>,
+    #[description = "The user or role"] target: Mentionable
+) -> DefaultCommandResult {
+    let left = match &ctx.interaction.data {
+        Some(InteractionData::ApplicationCommand(data)) => data.resolved.as_ref().map_or(0, |resolved| {
+            resolved.users.len() + resolved.members.len() + resolved.roles.len()
+        }),
+        _ => 0,
+    };
+
+    let mentioned = match target {
+        Mentionable::User(user, Some(_)) => format!("member {}", user.name),
+        Mentionable::User(user, None) => format!("user {}", user.name),
+        Mentionable::Role(role) => format!("role {}", role.name),
+    };
+
+    ctx.reply(format!("{}, {} resolved left", mentioned, left)).await?;
+    Ok(())
+}
+
+struct Lowercase;
+
+impl Validator for Lowercase {
+    const NAME: &'static str = "Lowercase";
+
+    fn validate(value: &str) -> Result<(), String> {
+        match value.chars().all(char::is_lowercase) {
+            true => Ok(()),
+            false => Err(String::from("Only lowercase letters are allowed")),
+        }
+    }
+}
+
+#[command]
+#[description = "Creates a tag"]
+async fn tag(
+    ctx: &mut SlashContext<()>,
+    #[description = "The name of the tag"] name: Length<2, 5>,
+    #[description = "The slug of the tag"] slug: Option<Validated<Lowercase>>
+) -> DefaultCommandResult {
+    let slug = slug.map(Validated::into_inner).unwrap_or_default();
+    ctx.reply(format!("{} {}", name.into_inner(), slug)).await?;
+    Ok(())
+}
+
+fn framework(recorder: &Recorder) -> Framework<()> {
+    Framework::builder(recorder.client(), APPLICATION_ID, ())
+        .command(nickname)
+        .command(whois)
+        .command(nickname_target)
+        .command(mention)
+        .command(tag)
+        .build()
+}
+
+fn nicked(nick: &str) -> InteractionMember {
+    let mut member = mock::member(Vec::new(), Permissions::empty());
+    member.nick = Some(String::from(nick));
+    member
+}
+
+fn reply(recorder: &Recorder) -> String {
+    let response = recorder.initial_response().expect("The command did not respond");
+    response.data.unwrap().content.unwrap()
+}
+
+fn parse_error(result: ProcessResult<(), DefaultError>) -> String {
+    let ProcessResult::CommandExecuted(result) = result else {
+        panic!("The command was not executed");
+    };
+
+    match result.output {
+        OutputLocation::Present(Err(FrameworkError::Parse(ParseError::Parsing { error, .. }))) => error,
+        _ => panic!("The arguments did not fail to parse"),
+    }
+}
+
+#[tokio::test]
+async fn parses_guild_members() {
+    let recorder = Recorder::start().await;
+    let user = Id::new(20);
+
+    let interaction = InteractionBuilder::chat("nickname")
+        .guild(Id::new(30))
+        .option("member", user)
+        .resolved_user(mock::user(user, "member"))
+        .resolved_member(user, nicked("Nick"))
+        .build();
+
+    framework(&recorder).process(interaction).await;
+
+    assert_eq!(reply(&recorder), "Nick");
+}
+
+#[tokio::test]
+async fn rejects_users_outside_the_guild() {
+    let recorder = Recorder::start().await;
+    let framework = framework(&recorder);
+    let user = Id::new(20);
+
+    let interaction = InteractionBuilder::chat("nickname")
+        .guild(Id::new(30))
+        .option("member", user)
+        .resolved_user(mock::user(user, "stranger"))
+        .build();
+
+    let result = framework.process(interaction).await;
+    assert_eq!(parse_error(result), "User is not a member of this guild");
+    assert!(recorder.initial_response().is_none());
+
+    let interaction = InteractionBuilder::chat("whois")
+        .guild(Id::new(30))
+        .option("user", user)
+        .resolved_user(mock::user(user, "stranger"))
+        .build();
+
+    framework.process(interaction).await;
+    assert_eq!(reply(&recorder), "stranger is not a member");
+}
+
+#[tokio::test]
+async fn resolves_members_when_available() {
+    let recorder = Recorder::start().await;
+    let user = Id::new(20);
+
+    let interaction = InteractionBuilder::chat("whois")
+        .guild(Id::new(30))
+        .option("user", user)
+        .resolved_user(mock::user(user, "member"))
+        .resolved_member(user, nicked("Nick"))
+        .build();
+
+    framework(&recorder).process(interaction).await;
+
+    assert_eq!(reply(&recorder), "member is a member");
+}
+
+#[tokio::test]
+async fn members_require_a_guild() {
+    let recorder = Recorder::start().await;
+    let framework = framework(&recorder);
+    let user = Id::new(20);
+
+    let interaction = InteractionBuilder::chat("nickname")
+        .option("member", user)
+        .resolved_user(mock::user(user, "friend"))
+        .build();
+
+    let result = framework.process(interaction).await;
+    assert_eq!(parse_error(result), "Members can only be used in guilds");
+
+    let interaction = InteractionBuilder::chat("whois")
+        .option("user", user)
+        .resolved_user(mock::user(user, "friend"))
+        .build();
+
+    framework.process(interaction).await;
+    assert_eq!(reply(&recorder), "friend is not a member");
+}
+
+#[tokio::test]
+async fn resolves_user_command_targets_to_members() {
+    let recorder = Recorder::start().await;
+    let framework = framework(&recorder);
+    let target = Id::new(20);
+
+    let interaction = InteractionBuilder::user("Nickname", target)
+        .guild(Id::new(30))
+        .resolved_member(target, nicked("Target"))
+        .build();
+
+    framework.process(interaction).await;
+    assert_eq!(reply(&recorder), "Target");
+
+    let interaction = InteractionBuilder::user("Nickname", target)
+        .guild(Id::new(30))
+        .build();
+
+    let result = framework.process(interaction).await;
+    assert_eq!(parse_error(result), "User is not a member of this guild");
+}
+
+#[tokio::test]
+async fn parses_mentioned_members() {
+    let recorder = Recorder::start().await;
+    let user = Id::new(20);
+
+    let interaction = InteractionBuilder::chat("mention")
+        .guild(Id::new(30))
+        .option("target", user.cast::<GenericMarker>())
+        .resolved_user(mock::user(user, "member"))
+        .resolved_member(user, nicked("Nick"))
+        .build();
+
+    framework(&recorder).process(interaction).await;
+
+    assert_eq!(reply(&recorder), "member member, 0 resolved left");
+}
+
+#[tokio::test]
+async fn parses_mentioned_users() {
+    let recorder = Recorder::start().await;
+    let user = Id::new(20);
+
+    let interaction = InteractionBuilder::chat("mention")
+        .option("target", user.cast::<GenericMarker>())
+        .resolved_user(mock::user(user, "friend"))
+        .build();
+
+    framework(&recorder).process(interaction).await;
+
+    assert_eq!(reply(&recorder), "user friend, 0 resolved left");
+}
+
+#[tokio::test]
+async fn parses_mentioned_roles() {
+    let recorder = Recorder::start().await;
+    let role = Id::new(40);
+
+    let interaction = InteractionBuilder::chat("mention")
+        .guild(Id::new(30))
+        .option("target", role.cast::<GenericMarker>())
+        .resolved_role(mock::role(role, "Moderators", Permissions::empty()))
+        .resolved_user(mock::user(Id::new(20), "bystander"))
+        .build();
+
+    framework(&recorder).process(interaction).await;
+
+    // Only the mentioned role is taken from the resolved data.
+    assert_eq!(reply(&recorder), "role Moderators, 1 resolved left");
+}
+
+#[tokio::test]
+async fn limits_the_length_in_characters() {
+    let recorder = Recorder::start().await;
+    let framework = framework(&recorder);
+
+    let result = framework.process(InteractionBuilder::chat("tag").option("name", "a").build()).await;
+    assert_eq!(parse_error(result), "Input must be between 2 and 5 characters long");
+
+    let result = framework.process(InteractionBuilder::chat("tag").option("name", "abcdef").build()).await;
+    assert_eq!(parse_error(result), "Input must be between 2 and 5 characters long");
+
+    // Five characters, but ten bytes.
+    framework.process(InteractionBuilder::chat("tag").option("name", "ééééé").build()).await;
+    assert_eq!(reply(&recorder), "ééééé ");
+}
+
+#[tokio::test]
+async fn validates_strings() {
+    let recorder = Recorder::start().await;
+    let framework = framework(&recorder);
+
+    let interaction = || InteractionBuilder::chat("tag").option("name", "rust");
+
+    let result = framework.process(interaction().option("slug", "Rust").build()).await;
+    assert_eq!(parse_error(result), "Only lowercase letters are allowed");
+
+    framework.process(interaction().option("slug", "rust").build()).await;
+    assert_eq!(reply(&recorder), "rust rust");
+}
@@ -3,6 +3,8 @@ use crate::twilight_exports::*;
 use crate::parse::Parse;
 use crate::localizations::{Localizations, LocalizationsProvider};
 use crate::prelude::Framework;
+use std::any::Any;
+use std::fmt::{self, Debug, Formatter};
 
 /// A structure representing a command argument.
 pub struct CommandArgument<D, T, E> {
@@ -109,3 +111,89 @@ impl<D: Send + Sync, T, E> CommandArgument<D, T, E> {
         self
     }
 }
+
+/// A value of a [parsed argument](ParsedArgument), which can be shown using its `Debug`
+/// implementation or downcast into its type using [as_any](Self::as_any).
+pub trait ArgumentValue: Debug + Send + Sync {
+    /// Gets the value as [Any], to downcast it into its type.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<V: Any + Debug + Send + Sync> ArgumentValue for V {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// An argument of a command after being parsed, recorded by the command so its hooks and error
+/// handlers can see the values it received.
+///
+/// Values implementing `Clone` and `Debug` are recorded as a copy of themselves, which can be
+/// downcast using [downcast_ref](Self::downcast_ref). Values only implementing `Debug` are
+/// recorded as their debug representation, and other values as the name of their type.
+#[derive(Debug)]
+pub struct ParsedArgument {
+    /// The name of the argument, as registered in discord.
+    pub name: &'static str,
+    /// The value the argument was parsed into.
+    pub value: Box<dyn ArgumentValue>,
+}
+
+impl ParsedArgument {
+    #[doc(hidden)]
+    pub fn new(name: &'static str, value: Box<dyn ArgumentValue>) -> Self {
+        Self { name, value }
+    }
+
+    /// Gets the value of the argument if it was recorded as the given type.
+    pub fn downcast_ref<V: Any>(&self) -> Option<&V> {
+        (*self.value).as_any().downcast_ref()
+    }
+}
+
+/// The debug representation of a value which can't be copied, shown without quotes.
+struct Rendered(String);
+
+impl Debug for Rendered {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Used by the command macro to record each parsed argument in the most precise way its type
+// allows, calling `(&&&Record(&value)).record()` so the first applicable implementation is used.
+#[doc(hidden)]
+pub struct Record<'a, V>(pub &'a V);
+
+#[doc(hidden)]
+pub trait RecordValue {
+    fn record(&self) -> Box<dyn ArgumentValue>;
+}
+
+impl<V: Clone + Debug + Send + Sync + 'static> RecordValue for &&Record<'_, V> {
+    fn record(&self) -> Box<dyn ArgumentValue> {
+        Box::new(self.0.clone())
+    }
+}
+
+#[doc(hidden)]
+pub trait RecordDebug {
+    fn record(&self) -> Box<dyn ArgumentValue>;
+}
+
+impl<V: Debug> RecordDebug for &Record<'_, V> {
+    fn record(&self) -> Box<dyn ArgumentValue> {
+        Box::new(Rendered(format!("{:?}", self.0)))
+    }
+}
+
+#[doc(hidden)]
+pub trait RecordOpaque {
+    fn record(&self) -> Box<dyn ArgumentValue>;
+}
+
+impl<V> RecordOpaque for Record<'_, V> {
+    fn record(&self) -> Box<dyn ArgumentValue> {
+        Box::new(Rendered(format!("<{}>", std::any::type_name::<V>())))
+    }
+}
@@ -1,13 +1,20 @@
 use crate::{
+    check::{default_denial_response, DenialResponder},
     command::{Command, CommandMap},
+    component::ComponentHandler,
+    custom_id::CustomIdCodec,
+    cooldown::{CooldownStorage, MemoryCooldownStorage},
+    defer::Defer,
+    error_response::ErrorResponder,
     framework::{DefaultError, Framework},
     group::*,
-    hook::{AfterHook, BeforeHook},
+    hook::{AfterHook, BeforeHook, CooldownHook, ErrorHandlerHook},
+    middleware::{Layer, Layers},
     twilight_exports::{ApplicationMarker, Client, CommandType, Id, Permissions},
-    parse::ParseError
+    response::Reply
 };
 
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Duration};
 
 /// A wrapper around twilight's http client allowing the user to decide how to provide it to the framework.
 #[allow(clippy::large_enum_variant)]
@@ -79,15 +86,38 @@ pub struct FrameworkBuilder<D, T = (), E = DefaultError> {
     /// All groups containing commands.
     pub groups: GroupParentMap<D, T, E>,
     /// A hook executed before any command.
-    pub before: Option<BeforeHook<D>>,
+    pub before: Option<BeforeHook<D, T, E>>,
     /// A hook executed after command's completion.
     pub after: Option<AfterHook<D, T, E>>,
+    /// The handlers of component and modal interactions.
+    pub components: Vec<ComponentHandler<D, T, E>>,
+    /// The codec used to encode and decode typed custom ids.
+    pub custom_ids: CustomIdCodec,
+    /// The storage used to keep track of command cooldowns.
+    pub cooldown_storage: Box<dyn CooldownStorage>,
+    /// A hook used to tell the user a command is on cooldown.
+    pub cooldown_responder: Option<CooldownHook<D>>,
+    /// The error handler used when a command or component handler does not have its own.
+    pub error_handler: Option<ErrorHandlerHook<D, T, E>>,
+    /// The function creating the message shown to the user when no error handler takes an error,
+    /// if any.
+    pub error_responder: Option<ErrorResponder>,
+    /// The function creating the message shown to the user when a check denies the execution.
+    pub denial_responder: DenialResponder,
+    /// When the interactions of commands without their own policy are automatically deferred.
+    pub auto_defer: Option<Defer>,
+    /// The response sent when a command subject to a defer policy finishes without responding.
+    pub fallback_response: Reply,
+    /// Whether the permissions of the member are checked against the required permissions of
+    /// the command every time it is used.
+    pub strict_permissions: bool,
+    /// The maximum time commands without their own timeout can run.
+    pub command_timeout: Option<Duration>,
+    /// The layers wrapping the execution of every command.
+    pub layers: Layers<D, T, E>,
 }
 
-impl<D, T, E> FrameworkBuilder<D, T, E>
-where
-    E: From<ParseError>
-{
+impl<D, T, E> FrameworkBuilder<D, T, E> {
     /// Creates a new [Builder](self::FrameworkBuilder).
     pub fn new(
         http_client: impl Into<WrappedClient>,
@@ -102,6 +132,20 @@ where
             groups: Default::default(),
             before: None,
             after: None,
+            components: Vec::new(),
+            custom_ids: CustomIdCodec::new(),
+            cooldown_storage: Box::new(MemoryCooldownStorage::new()),
+            cooldown_responder: None,
+            error_handler: None,
+            error_responder: None,
+            denial_responder: default_denial_response,
+            auto_defer: None,
+            fallback_response: Reply::new()
+                .content("The command finished without sending a response.")
+                .ephemeral(),
+            strict_permissions: false,
+            command_timeout: None,
+            layers: Vec::new(),
         }
     }
 
@@ -109,14 +153,14 @@ where
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use vesper::prelude::*;
     /// use twilight_http::Client;
     /// use twilight_model::id::Id;
     ///
     /// #[before]
-    /// async fn before_hook(ctx: &mut SlashContext<()>, command_name: &str) -> bool {
-    ///     println!("Executing command {command_name}");
+    /// async fn before_hook(ctx: &mut SlashContext<()>, info: &CommandInfo<()>) -> bool {
+    ///     println!("Executing command {}", info.path());
     ///     true
     /// }
     ///
@@ -131,23 +175,24 @@ where
     ///         .build();
     /// }
     /// ```
-    pub fn before(mut self, fun: FnPointer<BeforeHook<D>>) -> Self {
+    pub fn before(mut self, fun: FnPointer<BeforeHook<D, T, E>>) -> Self {
         self.before = Some(fun());
         self
     }
 
-    /// Set the hook that will be executed after command's completion.
+    /// Set the hook that will be executed after command's completion, or once its execution is
+    /// denied, receiving the [denial](crate::check::Denial) as its optional fourth argument.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use vesper::prelude::*;
     /// use twilight_http::Client;
     /// use twilight_model::id::Id;
     ///
     /// #[after]
-    /// async fn after_hook(ctx: &mut SlashContext<()>, command_name: &str, _: Option<DefaultCommandResult>) {
-    ///     println!("Command {command_name} finished execution");
+    /// async fn after_hook(ctx: &mut SlashContext<()>, info: &CommandInfo<()>, _: Option<DefaultFrameworkResult>) {
+    ///     println!("Command {} finished execution", info.path());
     /// }
     ///
     /// #[tokio::main]
@@ -166,11 +211,244 @@ where
         self
     }
 
+    /// Set the storage used to keep track of command [cooldowns](crate::cooldown::Cooldown),
+    /// by default they are stored in memory.
+    pub fn cooldown_storage(mut self, storage: impl CooldownStorage + 'static) -> Self {
+        self.cooldown_storage = Box::new(storage);
+        self
+    }
+
+    /// Set the hook used to tell the user a command is on cooldown. By default, cooldowns are
+    /// told using the [denial responder](Self::denial_responder) as a
+    /// [cooldown denial](crate::check::Denial::Cooldown), which sends an ephemeral message telling
+    /// when the command can be used again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use vesper::prelude::*;
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[cooldown_responder]
+    /// async fn on_cooldown(ctx: &mut SlashContext<()>, remaining: Duration) {
+    ///     println!("Command on cooldown for {} seconds", remaining.as_secs());
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::<()>::builder(http_client, Id::new(app_id), ())
+    ///         .cooldown_responder(on_cooldown)
+    ///         .build();
+    /// }
+    /// ```
+    pub fn cooldown_responder(mut self, fun: FnPointer<CooldownHook<D>>) -> Self {
+        self.cooldown_responder = Some(fun());
+        self
+    }
+
+    /// Set the error handler used when a command or component handler does not have its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::prelude::*;
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[error_handler]
+    /// async fn handle_errors(
+    ///     ctx: &mut SlashContext<()>,
+    ///     info: &CommandInfo<()>,
+    ///     error: FrameworkError<Box<dyn std::error::Error + Send + Sync>>
+    /// ) {
+    ///     println!("Command {} failed: {error}", info.path());
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::builder(http_client, Id::new(app_id), ())
+    ///         .error_handler(handle_errors)
+    ///         .build();
+    /// }
+    /// ```
+    pub fn error_handler(mut self, fun: FnPointer<ErrorHandlerHook<D, T, E>>) -> Self {
+        self.error_handler = Some(fun());
+        self
+    }
+
+    /// Set the function creating the message shown to the user when a command fails and no error
+    /// handler takes the error. By default, no message is sent, while
+    /// [default_error_response](crate::error_response::default_error_response) sends an ephemeral
+    /// message describing the error along with its [id](crate::error_response::ErrorId).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::{prelude::*, error_response::{default_error_response, ErrorKind}};
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::<()>::builder(http_client, Id::new(app_id), ())
+    ///         .error_responder(|interaction, kind, id| match kind {
+    ///             ErrorKind::Command => Some(Reply::from(format!("Something went wrong ({id})")).ephemeral()),
+    ///             _ => default_error_response(interaction, kind, id),
+    ///         })
+    ///         .build();
+    /// }
+    /// ```
+    pub fn error_responder(mut self, fun: ErrorResponder) -> Self {
+        self.error_responder = Some(fun);
+        self
+    }
+
+    /// Set the function creating the message shown to the user when a
+    /// [check](crate::hook::CheckHook) denies the execution. By default, an ephemeral message
+    /// describing the [denial](crate::check::Denial) is sent, unless the check returned `false`
+    /// without a reason or already responded the interaction.
+    ///
+    /// The interaction is given to the function, so the message can be localized using its locale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::{prelude::*, check::{default_denial_response, Denial}};
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::<()>::builder(http_client, Id::new(app_id), ())
+    ///         .denial_responder(|interaction, denial| match (interaction.locale.as_deref(), denial) {
+    ///             (Some("es-ES"), Denial::Unspecified) => Some(Reply::from("No puedes usar este comando").ephemeral()),
+    ///             _ => default_denial_response(interaction, denial),
+    ///         })
+    ///         .build();
+    /// }
+    /// ```
+    pub fn denial_responder(mut self, fun: DenialResponder) -> Self {
+        self.denial_responder = fun;
+        self
+    }
+
+    /// Set the policy used to automatically defer the interactions of commands, which applies to
+    /// all the commands not having their own. By default interactions are not deferred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::prelude::*;
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::<()>::builder(http_client, Id::new(app_id), ())
+    ///         .auto_defer(Defer::before_deadline().ephemeral())
+    ///         .build();
+    /// }
+    /// ```
+    pub fn auto_defer(mut self, defer: Defer) -> Self {
+        self.auto_defer = Some(defer);
+        self
+    }
+
+    /// Set the response sent when a command subject to a [defer policy](Defer) finishes without
+    /// responding the interaction. Deferred interactions are not responded by the fallback.
+    pub fn fallback_response(mut self, reply: impl Into<Reply>) -> Self {
+        self.fallback_response = reply.into();
+        self
+    }
+
+    /// Set whether the permissions of the member are checked against the
+    /// [required permissions](crate::command::Command::required_permissions) of the command every
+    /// time it is used. By default they are only used as the default permissions of the command
+    /// when registering it, which guild administrators can override.
+    pub fn strict_permissions(mut self, strict: bool) -> Self {
+        self.strict_permissions = strict;
+        self
+    }
+
+    /// Set the maximum time commands without their own timeout and component handlers can run.
+    /// Once it elapses, the execution is stopped and its error handler receives a
+    /// [timeout error](crate::error::FrameworkError::TimedOut). By default commands have no
+    /// timeout.
+    pub fn command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = Some(timeout);
+        self
+    }
+
+    /// Adds a [layer](crate::middleware::Layer) wrapping the execution of every command. Layers
+    /// run in the order they are added, before the ones of groups and commands.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::prelude::*;
+    /// use vesper::command::ExecutionResult;
+    /// use vesper::framework::DefaultError;
+    /// use vesper::middleware::{Layer, Next};
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// struct Logging;
+    ///
+    /// #[async_trait]
+    /// impl Layer<(), (), DefaultError> for Logging {
+    ///     async fn call(
+    ///         &self,
+    ///         ctx: &mut SlashContext<'_, ()>,
+    ///         next: Next<'_, (), (), DefaultError>
+    ///     ) -> ExecutionResult<(), DefaultError> {
+    ///         println!("Executing [{}]", next.command().name);
+    ///         next.run(ctx).await
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::<()>::builder(http_client, Id::new(app_id), ())
+    ///         .layer(Logging)
+    ///         .build();
+    /// }
+    /// ```
+    pub fn layer(mut self, layer: impl Layer<D, T, E> + 'static) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
     /// Registers a new command in the framework.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use vesper::prelude::*;
     /// use twilight_http::Client;
     /// use twilight_model::id::Id;
@@ -181,7 +459,6 @@ where
     ///     ctx.defer(false).await?;
     ///     ctx.interaction_client.update_response(&ctx.interaction.token)
     ///         .content(Some("Hello world!"))
-    ///         .unwrap()
     ///         .await?;
     ///
     ///     Ok(())
@@ -197,7 +474,6 @@ where
     ///     ctx.defer(false).await?;
     ///     ctx.interaction_client.update_response(&ctx.interaction.token)
     ///         .content(Some(&c))
-    ///         .unwrap()
     ///         .await?;
     ///     Ok(())
     ///}
@@ -223,6 +499,49 @@ where
         self
     }
 
+    /// Registers a new handler of component or modal interactions, routed by their custom id.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::prelude::*;
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[component("vote:")]
+    /// async fn vote(ctx: &mut SlashContext<()>, option: u32) -> DefaultCommandResult {
+    ///     println!("Voted for option {option}");
+    ///     Ok(())
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///     let http_client = Client::new(token);
+    ///
+    ///     let framework = Framework::builder(http_client, Id::new(app_id), ())
+    ///         .component(vote)
+    ///         .build();
+    /// }
+    /// ```
+    pub fn component(mut self, fun: FnPointer<ComponentHandler<D, T, E>>) -> Self {
+        let handler = fun();
+        if self.components.iter().any(|h| h.kind == handler.kind && h.route == handler.route) {
+            panic!("A handler with the same route as {} is already registered", handler.name);
+        }
+        self.components.push(handler);
+        self
+    }
+
+    /// Set the key used to sign [typed custom ids](crate::custom_id::CustomId), preventing users
+    /// from forging them. By default custom ids are not signed.
+    #[cfg(feature = "signed-custom-id")]
+    pub fn custom_id_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.custom_ids = CustomIdCodec::signed(key);
+        self
+    }
+
     /// Registers a new group of commands.
     pub fn group<F>(mut self, fun: F) -> Self
     where
@@ -252,6 +571,8 @@ pub struct GroupParentBuilder<D, T, E> {
     description: Option<&'static str>,
     kind: ParentType<D, T, E>,
     required_permissions: Option<Permissions>,
+    required_bot_permissions: Option<Permissions>,
+    layers: Layers<D, T, E>,
     nsfw: bool,
     only_guilds: bool
 }
@@ -264,6 +585,8 @@ impl<D, T, E> GroupParentBuilder<D, T, E> {
             description: None,
             kind: ParentType::Group(Default::default()),
             required_permissions: None,
+            required_bot_permissions: None,
+            layers: Vec::new(),
             nsfw: false,
             only_guilds: false
         }
@@ -286,6 +609,19 @@ impl<D, T, E> GroupParentBuilder<D, T, E> {
         self
     }
 
+    /// Sets the permissions the bot needs to execute the commands of this group.
+    pub fn required_bot_permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.required_bot_permissions = Some(permissions);
+        self
+    }
+
+    /// Adds a [layer](crate::middleware::Layer) wrapping the execution of the commands of this
+    /// group, inside the ones of the framework.
+    pub fn layer(&mut self, layer: impl Layer<D, T, E> + 'static) -> &mut Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
     pub fn nsfw(&mut self, nsfw: bool) -> &mut Self {
         self.nsfw = nsfw;
         self
@@ -339,6 +675,8 @@ impl<D, T, E> GroupParentBuilder<D, T, E> {
             description: self.description.unwrap(),
             kind: self.kind,
             required_permissions: self.required_permissions,
+            required_bot_permissions: self.required_bot_permissions,
+            layers: self.layers,
             nsfw: self.nsfw,
             only_guilds: self.only_guilds
         }
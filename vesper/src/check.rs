@@ -0,0 +1,166 @@
+use crate::{
+    response::Reply,
+    twilight_exports::{ChannelMarker, Id, Interaction, Permissions, RoleMarker},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A function used to create the message shown to the user when a check denies the execution.
+/// Returning `None` sends no message for that denial.
+pub type DenialResponder = fn(&Interaction, &Denial) -> Option<Reply>;
+
+/// The reason a check denied the execution of a command.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Denial {
+    /// The user does not have any of the required roles.
+    MissingRoles(Vec<Id<RoleMarker>>),
+    /// The command can't be used in this channel, containing the channels where it can be used.
+    WrongChannel(Vec<Id<ChannelMarker>>),
+    /// The command can't be used yet, containing the remaining time until it can be used. This
+    /// is the denial of executions rejected by the [cooldown](crate::cooldown::Cooldown) of the
+    /// command, and can also be returned by checks implementing their own limits.
+    Cooldown(Duration),
+    /// The member is missing permissions required by the command.
+    MissingPermissions(Permissions),
+    /// The bot is missing permissions required by the command.
+    MissingBotPermissions(Permissions),
+    /// The command reached its limit of executions running at the same time.
+    ConcurrencyLimited,
+    /// The command can only be used in guilds.
+    GuildOnly,
+    /// The command can only be used in direct messages.
+    DmOnly,
+    /// The command can only be used by the owners of the application.
+    OwnerOnly,
+    /// The command can only be used while connected to a voice channel.
+    NotInVoice,
+    /// A custom message shown to the user.
+    Message(String),
+    /// The check returned `false` without giving a reason.
+    Unspecified,
+}
+
+/// The outcome of a [check](crate::hook::CheckHook).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// The check passed, allowing the execution.
+    Passed,
+    /// The check denied the execution for the given reason.
+    Denied(Denial),
+}
+
+impl CheckOutcome {
+    /// Denies the execution showing the given message to the user.
+    pub fn deny(message: impl Into<String>) -> Self {
+        Self::Denied(Denial::Message(message.into()))
+    }
+
+    /// Returns whether the check passed.
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed)
+    }
+}
+
+impl From<bool> for CheckOutcome {
+    fn from(passed: bool) -> Self {
+        if passed {
+            Self::Passed
+        } else {
+            Self::Denied(Denial::Unspecified)
+        }
+    }
+}
+
+impl From<Denial> for CheckOutcome {
+    fn from(denial: Denial) -> Self {
+        Self::Denied(denial)
+    }
+}
+
+/// Converts the output of a check function into the result expected by the framework, allowing
+/// checks to return either a `bool` or a [CheckOutcome].
+#[doc(hidden)]
+pub trait IntoCheckResult<E> {
+    fn into_check_result(self) -> Result<CheckOutcome, E>;
+}
+
+impl<E> IntoCheckResult<E> for Result<bool, E> {
+    fn into_check_result(self) -> Result<CheckOutcome, E> {
+        self.map(CheckOutcome::from)
+    }
+}
+
+impl<E> IntoCheckResult<E> for Result<CheckOutcome, E> {
+    fn into_check_result(self) -> Result<CheckOutcome, E> {
+        self
+    }
+}
+
+/// The [responder](DenialResponder) used by default, sending an ephemeral message describing
+/// the denial. Checks returning `false` without a reason don't send any message.
+pub fn default_denial_response(_: &Interaction, denial: &Denial) -> Option<Reply> {
+    let message = match denial {
+        Denial::MissingRoles(roles) => {
+            let roles = roles
+                .iter()
+                .map(|role| format!("<@&{}>", role))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("You need one of the following roles to use this command: {}", roles)
+        }
+        Denial::WrongChannel(channels) if channels.is_empty() => {
+            String::from("This command can't be used in this channel.")
+        }
+        Denial::WrongChannel(channels) => {
+            let channels = channels
+                .iter()
+                .map(|channel| format!("<#{}>", channel))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("This command can only be used in {}", channels)
+        }
+        Denial::Cooldown(remaining) => {
+            let retry = (SystemTime::now() + *remaining)
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs() + 1)
+                .unwrap_or_default();
+
+            format!("This command is on cooldown, you can use it again <t:{}:R>.", retry)
+        }
+        Denial::MissingPermissions(permissions) => format!(
+            "You need the following permissions to use this command: {}",
+            permission_names(*permissions)
+        ),
+        Denial::MissingBotPermissions(permissions) => format!(
+            "I need the following permissions to run this command: {}",
+            permission_names(*permissions)
+        ),
+        Denial::ConcurrencyLimited => {
+            String::from("This command is already running, try again once it finishes.")
+        }
+        Denial::GuildOnly => String::from("This command can only be used in a server."),
+        Denial::DmOnly => String::from("This command can only be used in direct messages."),
+        Denial::OwnerOnly => String::from("This command can only be used by the owners of the bot."),
+        Denial::NotInVoice => String::from("You must be in a voice channel to use this command."),
+        Denial::Message(message) => message.clone(),
+        Denial::Unspecified => return None,
+    };
+
+    Some(Reply::new().content(message).ephemeral())
+}
+
+/// Formats the names of the given permissions, like `Manage Channels, Send Messages`.
+fn permission_names(permissions: Permissions) -> String {
+    permissions
+        .iter_names()
+        .map(|(name, _)| {
+            name.split('_')
+                .map(|word| format!("{}{}", &word[..1], word[1..].to_lowercase()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,300 @@
+//! Prebuilt [checks](CheckHook) and combinators to compose them.
+//!
+//! All of them can be used in the `checks` attribute of commands and component handlers, the
+//! combinators accepting other checks directly:
+//!
+//! ```rust
+//! use vesper::prelude::*;
+//! use vesper::checks::{any_of, guild_only, has_any_role, owner_only};
+//! use vesper::twilight_exports::Id;
+//!
+//! #[command]
+//! #[description = "Bans a user"]
+//! #[checks(guild_only, any_of(owner_only, has_any_role([Id::new(1234)])))]
+//! async fn ban(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    builder::WrappedClient,
+    check::{CheckOutcome, Denial},
+    context::SlashContext,
+    hook::CheckHook,
+    twilight_exports::{ChannelMarker, GuildMarker, Id, RoleMarker, UserMarker},
+};
+use std::{
+    error::Error,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// How long the check returned by [owner_only] keeps the owners of the application before
+/// fetching them again.
+pub const OWNERS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The owners of the application, along with when they were fetched.
+type OwnerCache = Mutex<Option<(Instant, Vec<Id<UserMarker>>)>>;
+
+/// Data able to tell the voice channel a user is connected to, usually backed by a cache. This
+/// must be implemented by the data of the framework to use the [in_voice] check.
+pub trait VoiceStates {
+    /// Returns the voice channel the given user is connected to in the given guild.
+    fn voice_channel(&self, guild: Id<GuildMarker>, user: Id<UserMarker>) -> Option<Id<ChannelMarker>>;
+}
+
+/// Creates a check from a synchronous function.
+fn from_fn<D, E, F>(fun: F) -> CheckHook<D, E>
+where
+    D: 'static,
+    E: Send + 'static,
+    F: Fn(&SlashContext<'_, D>) -> CheckOutcome + Send + Sync + 'static,
+{
+    CheckHook::new(move |ctx| {
+        let outcome = fun(ctx);
+        Box::pin(async move { Ok(outcome) })
+    })
+}
+
+/// Only allows using the command inside guilds.
+pub fn guild_only<D: 'static, E: Send + 'static>() -> CheckHook<D, E> {
+    from_fn(|ctx| match ctx.interaction.guild_id {
+        Some(_) => CheckOutcome::Passed,
+        None => Denial::GuildOnly.into(),
+    })
+}
+
+/// Only allows using the command in direct messages.
+pub fn dm_only<D: 'static, E: Send + 'static>() -> CheckHook<D, E> {
+    from_fn(|ctx| match ctx.interaction.guild_id {
+        Some(_) => Denial::DmOnly.into(),
+        None => CheckOutcome::Passed,
+    })
+}
+
+/// Only allows members having any of the given roles to use the command.
+pub fn has_any_role<D, E>(roles: impl IntoIterator<Item = Id<RoleMarker>>) -> CheckHook<D, E>
+where
+    D: 'static,
+    E: Send + 'static,
+{
+    let roles = roles.into_iter().collect::<Vec<_>>();
+
+    from_fn(move |ctx| {
+        let has_role = ctx
+            .interaction
+            .member
+            .as_ref()
+            .map(|member| member.roles.iter().any(|role| roles.contains(role)))
+            .unwrap_or(false);
+
+        if has_role {
+            CheckOutcome::Passed
+        } else {
+            Denial::MissingRoles(roles.clone()).into()
+        }
+    })
+}
+
+/// Only allows using the command in the given channels.
+pub fn in_channel<D, E>(channels: impl IntoIterator<Item = Id<ChannelMarker>>) -> CheckHook<D, E>
+where
+    D: 'static,
+    E: Send + 'static,
+{
+    let channels = channels.into_iter().collect::<Vec<_>>();
+
+    from_fn(move |ctx| {
+        let channel = ctx.interaction.channel.as_ref().map(|channel| channel.id);
+
+        if channel.is_some_and(|channel| channels.contains(&channel)) {
+            CheckOutcome::Passed
+        } else {
+            Denial::WrongChannel(channels.clone()).into()
+        }
+    })
+}
+
+/// Only allows users connected to a voice channel of the guild to use the command. The voice
+/// states are provided by the data of the framework, see [VoiceStates].
+pub fn in_voice<D, E>() -> CheckHook<D, E>
+where
+    D: VoiceStates + 'static,
+    E: Send + 'static,
+{
+    from_fn(|ctx: &SlashContext<'_, D>| {
+        let connected = ctx
+            .interaction
+            .guild_id
+            .zip(ctx.interaction.author_id())
+            .and_then(|(guild, user)| ctx.data.voice_channel(guild, user))
+            .is_some();
+
+        if connected {
+            CheckOutcome::Passed
+        } else {
+            Denial::NotInVoice.into()
+        }
+    })
+}
+
+/// Only allows the owners of the application, or the members of its team, to use the command.
+///
+/// The owners are fetched the first time the check runs and kept by the check for
+/// [OWNERS_TTL], see [owner_only_cached_for] to keep them for a different time. If they can't be
+/// fetched, the execution is denied.
+pub fn owner_only<D, E>() -> CheckHook<D, E>
+where
+    D: Sync + 'static,
+    E: Send + 'static,
+{
+    owner_only_cached_for(OWNERS_TTL)
+}
+
+/// Only allows the owners of the application, or the members of its team, to use the command,
+/// keeping the owners for the given time before fetching them again.
+pub fn owner_only_cached_for<D, E>(ttl: Duration) -> CheckHook<D, E>
+where
+    D: Sync + 'static,
+    E: Send + 'static,
+{
+    let cache = Arc::new(OwnerCache::default());
+
+    CheckHook::new(move |ctx| {
+        let cache = Arc::clone(&cache);
+
+        Box::pin(async move {
+            let Some(user) = ctx.interaction.author_id() else {
+                return Ok(Denial::OwnerOnly.into());
+            };
+
+            match is_owner(&cache, ttl, ctx.http_client, user).await {
+                Ok(true) => Ok(CheckOutcome::Passed),
+                Ok(false) => Ok(Denial::OwnerOnly.into()),
+                Err(why) => {
+                    warn!("Failed to fetch the owners of the application: {}", why);
+                    Ok(Denial::OwnerOnly.into())
+                }
+            }
+        })
+    })
+}
+
+/// Returns whether the given user is an owner of the application, fetching the owners if they
+/// are not cached or were fetched longer than `ttl` ago.
+async fn is_owner(
+    cache: &OwnerCache,
+    ttl: Duration,
+    client: &WrappedClient,
+    user: Id<UserMarker>,
+) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    // The lock is held while fetching, so concurrent executions wait for the same request.
+    let mut cache = cache.lock().await;
+
+    if let Some((fetched_at, owners)) = cache.as_ref() {
+        if fetched_at.elapsed() < ttl {
+            return Ok(owners.contains(&user));
+        }
+    }
+
+    let owners = fetch_owners(client).await?;
+    let is_owner = owners.contains(&user);
+    *cache = Some((Instant::now(), owners));
+
+    Ok(is_owner)
+}
+
+/// Fetches the owner of the application, along with the members of its team.
+async fn fetch_owners(client: &WrappedClient) -> Result<Vec<Id<UserMarker>>, Box<dyn Error + Send + Sync>> {
+    let application = client
+        .inner()
+        .current_user_application()
+        .await?
+        .model()
+        .await?;
+
+    let mut owners = application
+        .owner
+        .map(|owner| vec![owner.id])
+        .unwrap_or_default();
+
+    if let Some(team) = application.team {
+        owners.extend(team.members.into_iter().map(|member| member.user.id));
+    }
+
+    Ok(owners)
+}
+
+/// Passes if all the given checks pass, returning the outcome of the first one denying the
+/// execution otherwise. The checks run in order, stopping at the first denial.
+pub fn all_of<D, E>(checks: Vec<CheckHook<D, E>>) -> CheckHook<D, E>
+where
+    D: Sync + 'static,
+    E: Send + 'static,
+{
+    let checks = Arc::new(checks);
+
+    CheckHook::new(move |ctx| {
+        let checks = Arc::clone(&checks);
+
+        Box::pin(async move {
+            for check in checks.iter() {
+                let outcome = (check.0)(&mut *ctx).await?;
+                if !outcome.is_passed() {
+                    return Ok(outcome);
+                }
+            }
+
+            Ok(CheckOutcome::Passed)
+        })
+    })
+}
+
+/// Passes if any of the given checks passes, returning the outcome of the first one denying the
+/// execution otherwise. The checks run in order, stopping at the first one passing.
+pub fn any_of<D, E>(checks: Vec<CheckHook<D, E>>) -> CheckHook<D, E>
+where
+    D: Sync + 'static,
+    E: Send + 'static,
+{
+    let checks = Arc::new(checks);
+
+    CheckHook::new(move |ctx| {
+        let checks = Arc::clone(&checks);
+
+        Box::pin(async move {
+            let mut denial = None;
+
+            for check in checks.iter() {
+                let outcome = (check.0)(&mut *ctx).await?;
+                if outcome.is_passed() {
+                    return Ok(outcome);
+                }
+
+                denial.get_or_insert(outcome);
+            }
+
+            Ok(denial.unwrap_or(CheckOutcome::Denied(Denial::Unspecified)))
+        })
+    })
+}
+
+/// Passes if the given check denies the execution, and the other way around.
+pub fn not<D, E>(check: CheckHook<D, E>) -> CheckHook<D, E>
+where
+    D: Sync + 'static,
+    E: Send + 'static,
+{
+    CheckHook::new(move |ctx| {
+        let check = check.clone();
+
+        Box::pin(async move {
+            Ok(match (check.0)(ctx).await? {
+                CheckOutcome::Passed => CheckOutcome::Denied(Denial::Unspecified),
+                CheckOutcome::Denied(_) => CheckOutcome::Passed,
+            })
+        })
+    })
+}
@@ -0,0 +1,324 @@
+use crate::{
+    context::SlashContext,
+    twilight_exports::{
+        Id, Interaction, InteractionClient, InteractionData, InteractionResponse,
+        InteractionResponseType, InteractionType, MessageMarker, UserMarker,
+    },
+    wait::{next_id, remove_waker, WaiterWaker, WakerSender},
+};
+use futures_core::Stream;
+use parking_lot::Mutex;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    time::{Instant, Sleep},
+};
+
+/// A filter applied to the interactions received by a [collector](ComponentCollector).
+type Filter = Box<dyn Fn(&Interaction) -> bool + Send + 'static>;
+
+/// A builder of a [component collector](ComponentCollector), obtained using
+/// [SlashContext::collect_components](SlashContext::collect_components).
+///
+/// All the filters set must be satisfied for an interaction to be collected.
+#[must_use = "Collectors do nothing unless built"]
+pub struct ComponentCollectorBuilder<'ctx> {
+    waiters: Weak<Mutex<Vec<WaiterWaker>>>,
+    http_client: &'ctx InteractionClient<'ctx>,
+    invoker: Option<Id<UserMarker>>,
+    message: Option<Id<MessageMarker>>,
+    custom_id_prefix: Option<String>,
+    only_invoker: bool,
+    filter: Option<Filter>,
+    max_items: Option<usize>,
+    idle_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+impl<'ctx> ComponentCollectorBuilder<'ctx> {
+    pub(crate) fn new<D>(ctx: &'ctx SlashContext<'_, D>) -> Self {
+        Self {
+            waiters: Arc::downgrade(ctx.waiters),
+            http_client: &ctx.interaction_client,
+            invoker: ctx.interaction.author_id(),
+            message: None,
+            custom_id_prefix: None,
+            only_invoker: false,
+            filter: None,
+            max_items: None,
+            idle_timeout: None,
+            timeout: None,
+        }
+    }
+
+    /// Only collects interactions from components of the given message.
+    pub fn message(mut self, message: Id<MessageMarker>) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Only collects interactions which custom id starts with the given prefix.
+    pub fn custom_id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.custom_id_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only collects interactions created by the user who invoked the command.
+    pub fn only_invoker(mut self) -> Self {
+        self.only_invoker = true;
+        self
+    }
+
+    /// Only collects interactions satisfying the given closure.
+    pub fn filter<F>(mut self, fun: F) -> Self
+    where
+        F: Fn(&Interaction) -> bool + Send + 'static,
+    {
+        self.filter = Some(Box::new(fun));
+        self
+    }
+
+    /// Stops collecting after the given amount of interactions have been collected.
+    pub fn max_items(mut self, max: usize) -> Self {
+        self.max_items = Some(max);
+        self
+    }
+
+    /// Stops collecting if no interaction is collected during the given time.
+    pub fn idle_timeout(mut self, duration: Duration) -> Self {
+        self.idle_timeout = Some(duration);
+        self
+    }
+
+    /// Stops collecting after the given time, no matter how many interactions were collected.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+        self
+    }
+
+    /// Registers the collector in the framework, starting to collect interactions.
+    pub fn build(self) -> ComponentCollector<'ctx> {
+        let Self {
+            waiters,
+            http_client,
+            invoker,
+            message,
+            custom_id_prefix,
+            only_invoker,
+            filter,
+            max_items,
+            idle_timeout,
+            timeout,
+        } = self;
+
+        let predicate = move |interaction: &Interaction| {
+            if interaction.kind != InteractionType::MessageComponent {
+                return false;
+            }
+
+            if let Some(message) = message {
+                if interaction.message.as_ref().map(|m| m.id) != Some(message) {
+                    return false;
+                }
+            }
+
+            if let Some(prefix) = &custom_id_prefix {
+                let Some(InteractionData::MessageComponent(data)) = &interaction.data else {
+                    return false;
+                };
+
+                if !data.custom_id.starts_with(prefix.as_str()) {
+                    return false;
+                }
+            }
+
+            if only_invoker && (invoker.is_none() || interaction.author_id() != invoker) {
+                return false;
+            }
+
+            filter.as_ref().map(|fun| fun(interaction)).unwrap_or(true)
+        };
+
+        let (sender, receiver) = unbounded_channel();
+        let id = next_id();
+
+        if let Some(waiters) = waiters.upgrade() {
+            waiters.lock().push(WaiterWaker {
+                id,
+                predicate: Box::new(predicate),
+                sender: WakerSender::Stream(sender),
+            });
+        }
+
+        ComponentCollector {
+            id,
+            receiver,
+            waiters,
+            http_client,
+            collected: 0,
+            max_items,
+            idle_timeout: idle_timeout.map(|duration| (duration, Box::pin(tokio::time::sleep(duration)))),
+            deadline: timeout.map(|duration| Box::pin(tokio::time::sleep(duration))),
+            finished: false,
+        }
+    }
+}
+
+/// A collector of message component interactions.
+///
+/// Unlike an [interaction waiter](crate::wait::InteractionWaiter), the collector keeps receiving
+/// every matching interaction until one of its limits is reached, yielding them as a [`Stream`].
+/// The stream ends when the maximum amount of items is collected, when the idle or total timeouts
+/// elapse, or when the framework is dropped.
+///
+/// Collected interactions must be responded, this can be done using
+/// [acknowledge](Self::acknowledge) or [respond](Self::respond).
+///
+/// Dropping the collector stops collecting interactions.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use futures::StreamExt;
+/// use vesper::prelude::*;
+///
+/// #[command]
+/// #[description = "Starts a vote"]
+/// async fn vote(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+///     // Send a message with the voting buttons here.
+///
+///     let mut collector = ctx.collect_components()
+///         .custom_id_prefix("vote:")
+///         .idle_timeout(Duration::from_secs(30))
+///         .timeout(Duration::from_secs(300))
+///         .build();
+///
+///     let mut votes = 0;
+///
+///     while let Some(interaction) = collector.next().await {
+///         collector.acknowledge(&interaction).await?;
+///         votes += 1;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [`Stream`]: Stream
+#[must_use = "Collectors do nothing unless polled"]
+pub struct ComponentCollector<'ctx> {
+    id: u64,
+    receiver: UnboundedReceiver<Interaction>,
+    waiters: Weak<Mutex<Vec<WaiterWaker>>>,
+    http_client: &'ctx InteractionClient<'ctx>,
+    collected: usize,
+    max_items: Option<usize>,
+    idle_timeout: Option<(Duration, Pin<Box<Sleep>>)>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    finished: bool,
+}
+
+impl ComponentCollector<'_> {
+    /// Returns the amount of interactions collected so far.
+    pub fn collected(&self) -> usize {
+        self.collected
+    }
+
+    /// Acknowledges the given interaction without modifying the message.
+    pub async fn acknowledge(&self, interaction: &Interaction) -> Result<(), twilight_http::Error> {
+        self.respond(interaction, &InteractionResponse {
+            kind: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        })
+        .await
+    }
+
+    /// Responds the given interaction using the provided response.
+    pub async fn respond(
+        &self,
+        interaction: &Interaction,
+        response: &InteractionResponse,
+    ) -> Result<(), twilight_http::Error> {
+        self.http_client
+            .create_response(interaction.id, &interaction.token, response)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the maximum amount of items has been collected.
+    fn is_full(&self) -> bool {
+        self.max_items.map(|max| self.collected >= max).unwrap_or(false)
+    }
+
+    /// Stops collecting interactions, removing the collector from the framework.
+    pub fn stop(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            self.receiver.close();
+            remove_waker(&self.waiters, self.id);
+        }
+    }
+}
+
+impl Stream for ComponentCollector<'_> {
+    type Item = Interaction;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.finished || this.is_full() {
+            this.stop();
+            return Poll::Ready(None);
+        }
+
+        if let Poll::Ready(item) = this.receiver.poll_recv(cx) {
+            let Some(interaction) = item else {
+                this.stop();
+                return Poll::Ready(None);
+            };
+
+            this.collected += 1;
+
+            if this.is_full() {
+                this.stop();
+            } else if let Some((duration, sleep)) = this.idle_timeout.as_mut() {
+                sleep.as_mut().reset(Instant::now() + *duration);
+            }
+
+            return Poll::Ready(Some(interaction));
+        }
+
+        let idle = this
+            .idle_timeout
+            .as_mut()
+            .map(|(_, sleep)| sleep.as_mut().poll(cx).is_ready())
+            .unwrap_or(false);
+
+        let expired = this
+            .deadline
+            .as_mut()
+            .map(|sleep| sleep.as_mut().poll(cx).is_ready())
+            .unwrap_or(false);
+
+        if idle || expired {
+            this.stop();
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for ComponentCollector<'_> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -1,19 +1,36 @@
+use crate::check::{CheckOutcome, Denial};
+use crate::concurrency::Concurrency;
+use crate::cooldown::Cooldown;
+use crate::defer::Defer;
+use crate::error::FrameworkError;
+use crate::error_response::ErrorId;
 use crate::hook::{CheckHook, ErrorHandlerHook};
 use crate::localizations::{Localizations, LocalizationsProvider};
+use crate::middleware::{Layer, Layers};
+use crate::component::{ComponentHandler, ComponentKind};
+use crate::framework::DefaultError;
 use crate::prelude::{CreateCommandError, Framework};
-use crate::twilight_exports::{Command as TwilightCommand, CommandType};
+use crate::twilight_exports::{
+    Command as TwilightCommand, CommandDataOption, CommandOptionValue, CommandType,
+    InteractionData,
+};
+use crate::dispatch::{isolate, Dispatch};
 use crate::{
-    argument::CommandArgument, context::SlashContext, framework::ProcessResult,
+    argument::{CommandArgument, ParsedArgument}, context::SlashContext, framework::ProcessResult,
     twilight_exports::Permissions, BoxFuture,
 };
-use std::collections::HashMap;
-use tracing::{debug, info};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+use tracing::{debug, warn};
 use twilight_http::client::InteractionClient;
 use twilight_model::id::{marker::GuildMarker, Id};
 
 /// A pointer to a command function.
 pub(crate) type CommandFn<D, T, E> =
-    for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>) -> BoxFuture<'cx, Result<T, E>>;
+    for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>) -> BoxFuture<'cx, Result<T, FrameworkError<E>>>;
 /// A map of [commands](self::Command).
 pub type CommandMap<D, T, E> = HashMap<&'static str, Command<D, T, E>>;
 
@@ -29,7 +46,7 @@ macro_rules! if_some {
 
 /// Information about the execution state of a command.
 #[non_exhaustive]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ExecutionState {
     /// A check had an error.
     CheckErrored,
@@ -41,15 +58,32 @@ pub enum ExecutionState {
     CommandErrored,
     /// The `before` hook returned `false` and the command didn't execute.
     BeforeHookFailed,
+    /// The command did not finish before its timeout elapsed, containing the timeout.
+    TimedOut(Duration),
+    /// The command panicked while executing.
+    Panicked,
+    /// The command is on cooldown and didn't execute, containing the remaining time until it can
+    /// be used again.
+    OnCooldown(Duration),
+    /// The bot is missing permissions required by the command and it didn't execute, containing
+    /// the missing permissions.
+    MissingBotPermissions(Permissions),
+    /// The member is missing permissions required by the command and it didn't execute,
+    /// containing the missing permissions. Only checked when the framework uses strict
+    /// permissions.
+    MissingMemberPermissions(Permissions),
+    /// The command reached its [concurrency limit](Concurrency) and the execution was rejected.
+    ConcurrencyLimited,
 }
 
 /// The location of the output of the command.
 #[non_exhaustive]
+#[derive(Debug)]
 pub enum OutputLocation<T, E> {
     /// The command was not executed, thus there is not any output.
     NotExecuted,
     /// The output has not been taken by any hook.
-    Present(Result<T, E>),
+    Present(Result<T, FrameworkError<E>>),
     /// The output has been forwarded to the `after` hook.
     TakenByAfterHook,
     /// The output has been taken by the `error_handler` hook.
@@ -57,11 +91,19 @@ pub enum OutputLocation<T, E> {
 }
 
 /// Information about the command execution and it's output.
+#[derive(Debug)]
 pub struct ExecutionResult<T, E> {
     /// The execution state of the command.
     pub state: ExecutionState,
     /// The output of the command.
     pub output: OutputLocation<T, E>,
+    /// The id of the error, present when the command or its checks returned an error. This id is
+    /// logged and shown to the user by the default [error responder](crate::error_response::ErrorResponder).
+    pub error_id: Option<ErrorId>,
+    /// The reason the execution was denied, present when the state is
+    /// [CheckFailed](ExecutionState::CheckFailed), [OnCooldown](ExecutionState::OnCooldown),
+    /// [ConcurrencyLimited](ExecutionState::ConcurrencyLimited) or when permissions are missing.
+    pub denial: Option<Denial>,
 }
 
 impl<T, E> From<ExecutionResult<T, E>> for ProcessResult<T, E> {
@@ -70,6 +112,119 @@ impl<T, E> From<ExecutionResult<T, E>> for ProcessResult<T, E> {
     }
 }
 
+/// What an invocation given to the hooks executes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InvocationKind {
+    /// A command of the given type.
+    Command(CommandType),
+    /// A [component handler](ComponentHandler) of the given kind.
+    Component(ComponentKind),
+}
+
+/// Information about an invocation, given to the hooks and error handlers so they can tell apart
+/// the commands sharing the same name, like `/config set` and `/tag set`.
+pub struct CommandInfo<'a, D, T = (), E = DefaultError> {
+    /// The top level command when the command is a subcommand, like `config` in `/config set`.
+    pub parent: Option<String>,
+    /// The subcommand group containing the command, like `role` in `/config role add`.
+    pub group: Option<String>,
+    /// The name of the command or component handler.
+    pub name: &'a str,
+    /// What the invocation executes.
+    pub kind: InvocationKind,
+    /// The command being executed, `None` for component handlers.
+    pub command: Option<&'a Command<D, T, E>>,
+    /// The raw options given by the user, as received from discord. The values the command
+    /// parsed them into are available in [arguments](Self::arguments).
+    pub options: Vec<CommandDataOption>,
+    /// The arguments recorded by the command once parsed, shared with its context.
+    pub(crate) arguments: Arc<OnceLock<Vec<ParsedArgument>>>,
+}
+
+impl<'a, D, T, E> CommandInfo<'a, D, T, E> {
+    /// Creates the information of an invocation of the given command.
+    pub(crate) fn for_command(command: &'a Command<D, T, E>, context: &SlashContext<'_, D>) -> Self {
+        let mut info = Self {
+            parent: None,
+            group: None,
+            name: command.name,
+            kind: InvocationKind::Command(command.kind),
+            command: Some(command),
+            options: Vec::new(),
+            arguments: Arc::clone(&context.arguments),
+        };
+
+        let Some(InteractionData::ApplicationCommand(data)) = context.interaction.data.as_ref() else {
+            return info;
+        };
+
+        let mut options = &data.options;
+
+        while let Some(option) = options.first() {
+            match &option.value {
+                CommandOptionValue::SubCommandGroup(next) => {
+                    info.parent = Some(data.name.clone());
+                    info.group = Some(option.name.clone());
+                    options = next;
+                }
+                CommandOptionValue::SubCommand(next) => {
+                    info.parent = Some(data.name.clone());
+                    options = next;
+                }
+                _ => break,
+            }
+        }
+
+        info.options = options.clone();
+        info
+    }
+
+    /// Creates the information of an invocation of the given component handler.
+    pub(crate) fn for_component(handler: &'a ComponentHandler<D, T, E>) -> Self {
+        Self {
+            parent: None,
+            group: None,
+            name: handler.name,
+            kind: InvocationKind::Component(handler.kind),
+            command: None,
+            options: Vec::new(),
+            arguments: Default::default(),
+        }
+    }
+
+    /// Gets the full path of the command, including its parent and group, separated by spaces.
+    pub fn path(&self) -> String {
+        self.parent
+            .iter()
+            .chain(self.group.iter())
+            .map(String::as_str)
+            .chain(std::iter::once(self.name))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Gets the raw value given by the user to the option with the given name.
+    pub fn option(&self, name: &str) -> Option<&CommandOptionValue> {
+        self.options
+            .iter()
+            .find(|option| option.name == name)
+            .map(|option| &option.value)
+    }
+
+    /// Gets the arguments of the command after being parsed, in the order they are declared.
+    ///
+    /// The arguments are recorded once all of them have been parsed, so this is empty before
+    /// the command runs, for component handlers and when the arguments failed to parse.
+    pub fn arguments(&self) -> &[ParsedArgument] {
+        self.arguments.get().map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Gets the parsed argument with the given name.
+    pub fn argument(&self, name: &str) -> Option<&ParsedArgument> {
+        self.arguments().iter().find(|argument| argument.name == name)
+    }
+}
+
 /// A command executed by the framework.
 pub struct Command<D, T, E> {
     /// The name of the command.
@@ -85,10 +240,23 @@ pub struct Command<D, T, E> {
     pub fun: CommandFn<D, T, E>,
     /// The required permissions to use this command
     pub required_permissions: Option<Permissions>,
+    /// The permissions the bot needs to execute this command.
+    pub required_bot_permissions: Option<Permissions>,
     pub nsfw: bool,
     pub only_guilds: bool,
     pub checks: Vec<CheckHook<D, E>>,
-    pub error_handler: Option<ErrorHandlerHook<D, E>>,
+    pub error_handler: Option<ErrorHandlerHook<D, T, E>>,
+    /// The cooldown applied to this command.
+    pub cooldown: Option<Cooldown>,
+    /// The maximum time the command can run, overriding the timeout of the framework.
+    pub timeout: Option<Duration>,
+    /// The limit of executions of this command running at the same time.
+    pub concurrency: Option<Concurrency>,
+    /// When the interaction is automatically deferred, overriding the policy of the framework.
+    pub defer: Option<Defer>,
+    /// The layers wrapping the execution of this command, inside the ones of the framework and
+    /// its group.
+    pub layers: Layers<D, T, E>,
 }
 
 impl<D, T, E> Command<D, T, E> {
@@ -103,10 +271,16 @@ impl<D, T, E> Command<D, T, E> {
             arguments: Default::default(),
             fun,
             required_permissions: Default::default(),
+            required_bot_permissions: None,
             nsfw: false,
             only_guilds: false,
             checks: Default::default(),
             error_handler: None,
+            cooldown: None,
+            timeout: None,
+            concurrency: None,
+            defer: None,
+            layers: Vec::new(),
         }
     }
 
@@ -138,16 +312,54 @@ impl<D, T, E> Command<D, T, E> {
         self
     }
 
-    pub fn error_handler(mut self, hook: ErrorHandlerHook<D, E>) -> Self {
+    pub fn error_handler(mut self, hook: ErrorHandlerHook<D, T, E>) -> Self {
         self.error_handler = Some(hook);
         self
     }
 
+    /// Sets the cooldown of the command.
+    pub fn cooldown(mut self, cooldown: Cooldown) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Sets the maximum time the command can run before being stopped.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the limit of executions of the command running at the same time.
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Adds a [layer](Layer) wrapping the execution of the command, inside the ones added
+    /// before.
+    pub fn layer(mut self, layer: impl Layer<D, T, E> + 'static) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Sets when the interaction is automatically deferred.
+    pub fn defer(mut self, defer: Defer) -> Self {
+        self.defer = Some(defer);
+        self
+    }
+
     pub fn required_permissions(mut self, permissions: Permissions) -> Self {
         self.required_permissions = Some(permissions);
         self
     }
 
+    /// Sets the permissions the bot needs to execute the command, checked every time the command
+    /// is used.
+    pub fn required_bot_permissions(mut self, permissions: Permissions) -> Self {
+        self.required_bot_permissions = Some(permissions);
+        self
+    }
+
     pub fn nsfw(mut self, nsfw: bool) -> Self {
         self.nsfw = nsfw;
         self
@@ -199,16 +411,17 @@ impl<D, T, E> Command<D, T, E> {
     pub async fn run_checks<'cx, 'data: 'cx>(
         &self,
         context: &'cx mut SlashContext<'data, D>,
-    ) -> Result<bool, E> {
-        debug!("Running command [{}] checks", self.name);
-        for check in &self.checks {
-            if !(check.0)(context).await? {
-                debug!("Command [{}] check returned false", self.name);
-                return Ok(false);
-            }
+    ) -> Result<CheckOutcome, E> {
+        self.dispatch().run_checks(context).await
+    }
+
+    fn dispatch(&self) -> Dispatch<'_, D, T, E> {
+        Dispatch {
+            kind: "Command",
+            name: self.name,
+            checks: &self.checks,
+            error_handler: self.error_handler.as_ref(),
         }
-        debug!("All command [{}] checks passed", self.name);
-        Ok(true)
     }
 
     async fn create_chat_command(
@@ -223,10 +436,10 @@ impl<D, T, E> Command<D, T, E> {
             .map(|a| a.as_option(framework, self))
             .collect::<Vec<_>>();
 
-        let name_localizations = self.localized_names.get_localizations(framework, &self);
+        let name_localizations = self.localized_names.get_localizations(framework, self);
         let description_localizations = self
             .localized_descriptions
-            .get_localizations(framework, &self);
+            .get_localizations(framework, self);
 
         let model = if let Some(id) = guild {
             let mut command = http
@@ -340,71 +553,87 @@ impl<D, T, E> Command<D, T, E> {
         }
     }
 
-    pub async fn execute<'cx, 'data: 'cx>(
+    /// Runs the command function, deferring the interaction as specified by the given policy.
+    async fn run<'cx, 'data: 'cx>(
         &self,
         context: &'cx mut SlashContext<'data, D>,
-    ) -> ExecutionResult<T, E> {
-        let state;
-        let location;
-
-        match self.run_checks(context).await {
-            Ok(true) => {
-                debug!("Executing command [{}]", self.name);
-                let output = (self.fun)(context).await;
-
-                match (&self.error_handler, output) {
-                    (Some(hook), Err(why)) => {
-                        info!(
-                            "Command [{}] raised an error, using established error handler",
-                            self.name
-                        );
-                        state = ExecutionState::CommandErrored;
-                        location = OutputLocation::TakenByErrorHandler;
-
-                        (hook.0)(context, why).await;
-                    }
-                    (_, Ok(res)) => {
-                        debug!("Command [{}] executed successfully", self.name);
-                        state = ExecutionState::CommandFinished;
-                        location = OutputLocation::Present(Ok(res));
-                    }
-                    (_, Err(res)) => {
-                        info!(
-                            "Command [{}] raised an error, but no error handler was established",
-                            self.name
-                        );
-                        state = ExecutionState::CommandErrored;
-                        location = OutputLocation::Present(Err(res));
-                    }
-                };
-            }
-            Err(why) => {
-                state = ExecutionState::CheckErrored;
-                // If the command has an error handler, execute it, if not, discard the error.
-                if let Some(hook) = &self.error_handler {
-                    info!(
-                        "Command [{}] check raised an error, using established error handler",
-                        self.name
-                    );
-                    (hook.0)(context, why).await;
-                    location = OutputLocation::TakenByErrorHandler;
-                } else {
-                    info!(
-                        "Command [{}] check raised an error, but no error handler was established",
-                        self.name
-                    );
-                    location = OutputLocation::Present(Err(why));
-                }
-            }
-            _ => {
-                state = ExecutionState::CheckFailed;
-                location = OutputLocation::NotExecuted;
+        defer: Option<Defer>,
+    ) -> Result<T, FrameworkError<E>> {
+        let Some(defer) = defer else {
+            return (self.fun)(context).await;
+        };
+
+        // The command borrows the context mutably, so the interaction is deferred using a clone,
+        // which shares the response state with the original one.
+        let deferrer = context.clone();
+
+        if defer.delay.is_zero() {
+            self.defer_interaction(&deferrer, defer).await;
+            return (self.fun)(context).await;
+        }
+
+        let mut future = (self.fun)(context);
+
+        match tokio::time::timeout(defer.delay, &mut future).await {
+            Ok(output) => output,
+            Err(_) => {
+                self.defer_interaction(&deferrer, defer).await;
+                future.await
             }
         }
+    }
+
+    async fn defer_interaction(&self, context: &SlashContext<'_, D>, defer: Defer) {
+        debug!("Deferring command [{}]", self.name);
+        if let Err(why) = context.defer(defer.ephemeral).await {
+            warn!("Failed to defer command [{}]: {}", self.name, why);
+        }
+    }
 
-        ExecutionResult {
-            state,
-            output: location,
+    pub async fn execute<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+    ) -> ExecutionResult<T, E> {
+        let info = CommandInfo::for_command(self, context);
+        self.execute_with(context, &info, self.defer, self.timeout).await
+    }
+
+    /// Executes the command, deferring the interaction as specified by the given policy and
+    /// stopping it once the given timeout elapses.
+    pub(crate) async fn execute_with<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        defer: Option<Defer>,
+        timeout: Option<Duration>,
+    ) -> ExecutionResult<T, E> {
+        match self.pass_checks(context, info).await {
+            Ok(()) => self.run_checked(context, info, defer, timeout).await,
+            Err(result) => result,
         }
     }
+
+    /// Runs the checks of the command, returning the result of the execution if they did not
+    /// pass.
+    pub(crate) async fn pass_checks<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        info: &CommandInfo<'_, D, T, E>,
+    ) -> Result<(), ExecutionResult<T, E>> {
+        self.dispatch().pass_checks(context, info).await
+    }
+
+    /// Runs the command once its checks passed, catching its panics and stopping it once the
+    /// timeout elapses, giving its errors to the error handler.
+    pub(crate) async fn run_checked<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        defer: Option<Defer>,
+        timeout: Option<Duration>,
+    ) -> ExecutionResult<T, E> {
+        debug!("Executing command [{}]", self.name);
+        let output = isolate(self.run(context, defer), timeout).await;
+        self.dispatch().finish(context, info, output).await
+    }
 }
@@ -0,0 +1,192 @@
+use crate::{
+    check::CheckOutcome,
+    command::{CommandInfo, ExecutionResult},
+    context::SlashContext,
+    dispatch::{isolate, Dispatch},
+    error::FrameworkError,
+    hook::{CheckHook, ErrorHandlerHook},
+    twilight_exports::{Interaction, InteractionData, InteractionType},
+    BoxFuture,
+};
+use std::time::Duration;
+use tracing::debug;
+
+/// A pointer to a component handler function.
+pub(crate) type ComponentFn<D, T, E> =
+    for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>, &'cx str) -> BoxFuture<'cx, Result<T, FrameworkError<E>>>;
+
+/// The kind of interactions a [component handler](ComponentHandler) receives.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ComponentKind {
+    /// Message component interactions, such as buttons and select menus.
+    Component,
+    /// Modal submit interactions.
+    Modal,
+}
+
+impl ComponentKind {
+    /// Gets the kind of handler the given interaction must be routed to, along with its custom id.
+    pub(crate) fn of(interaction: &Interaction) -> Option<(Self, &str)> {
+        match (interaction.kind, interaction.data.as_ref()?) {
+            (InteractionType::MessageComponent, InteractionData::MessageComponent(data)) => {
+                Some((Self::Component, data.custom_id.as_str()))
+            }
+            (InteractionType::ModalSubmit, InteractionData::ModalSubmit(data)) => {
+                Some((Self::Modal, data.custom_id.as_str()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The custom ids a [component handler](ComponentHandler) is routed from.
+///
+/// A route matches all the custom ids starting with its prefix and ending with its suffix, the
+/// part of the custom id between them is the remainder given to the handler.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ComponentRoute {
+    prefix: &'static str,
+    suffix: &'static str,
+}
+
+impl ComponentRoute {
+    /// Creates a route matching all the custom ids starting with the given prefix.
+    pub const fn prefix(prefix: &'static str) -> Self {
+        Self { prefix, suffix: "" }
+    }
+
+    /// Creates a route from the given pattern, which must contain a single `*` matching the
+    /// remainder, for example `ticket:*:close`. A pattern without `*` only matches exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern contains more than one `*`.
+    pub fn pattern(pattern: &'static str) -> Self {
+        match pattern.split_once('*') {
+            Some((prefix, suffix)) => {
+                assert!(!suffix.contains('*'), "A route pattern can only contain a single `*`");
+                Self { prefix, suffix }
+            }
+            None => Self {
+                prefix: pattern,
+                suffix: "",
+            },
+        }
+    }
+
+    /// Returns the remainder of the given custom id if it matches this route.
+    pub fn matches<'a>(&self, custom_id: &'a str) -> Option<&'a str> {
+        custom_id
+            .strip_prefix(self.prefix)?
+            .strip_suffix(self.suffix)
+    }
+
+    /// The length of the fixed parts of the route, used to pick the most specific route when
+    /// several match the same custom id.
+    pub(crate) fn specificity(&self) -> usize {
+        self.prefix.len() + self.suffix.len()
+    }
+}
+
+/// A handler of component or modal interactions, routed by their custom id.
+///
+/// Unlike [waiters](crate::wait::InteractionWaiter), handlers are registered in the framework, so
+/// they keep working after a restart. The interactions are only routed to handlers when no waiter
+/// or collector claims them.
+pub struct ComponentHandler<D, T, E> {
+    /// The name of the handler, given to the hooks.
+    pub name: &'static str,
+    /// The kind of interactions this handler receives.
+    pub kind: ComponentKind,
+    /// The custom ids this handler is routed from.
+    pub route: ComponentRoute,
+    /// A pointer to this handler function.
+    pub fun: ComponentFn<D, T, E>,
+    pub checks: Vec<CheckHook<D, E>>,
+    pub error_handler: Option<ErrorHandlerHook<D, T, E>>,
+}
+
+impl<D, T, E> ComponentHandler<D, T, E> {
+    /// Creates a new component handler.
+    pub fn new(fun: ComponentFn<D, T, E>) -> Self {
+        Self {
+            name: Default::default(),
+            kind: ComponentKind::Component,
+            route: ComponentRoute::prefix(""),
+            fun,
+            checks: Default::default(),
+            error_handler: None,
+        }
+    }
+
+    /// Sets the handler name.
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the kind of interactions this handler receives.
+    pub fn kind(mut self, kind: ComponentKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the custom ids this handler is routed from.
+    pub fn route(mut self, route: ComponentRoute) -> Self {
+        self.route = route;
+        self
+    }
+
+    pub fn checks(mut self, checks: Vec<CheckHook<D, E>>) -> Self {
+        self.checks = checks;
+        self
+    }
+
+    pub fn error_handler(mut self, hook: ErrorHandlerHook<D, T, E>) -> Self {
+        self.error_handler = Some(hook);
+        self
+    }
+
+    pub async fn run_checks<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+    ) -> Result<CheckOutcome, E> {
+        self.dispatch().run_checks(context).await
+    }
+
+    pub async fn execute<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        remainder: &'cx str,
+    ) -> ExecutionResult<T, E> {
+        self.execute_with(context, &CommandInfo::for_component(self), remainder, None).await
+    }
+
+    /// Executes the handler, giving the given information to its error handler and stopping it
+    /// once the given timeout elapses.
+    pub(crate) async fn execute_with<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        remainder: &'cx str,
+        timeout: Option<Duration>,
+    ) -> ExecutionResult<T, E> {
+        let dispatch = self.dispatch();
+        if let Err(result) = dispatch.pass_checks(context, info).await {
+            return result;
+        }
+
+        debug!("Executing component handler [{}]", self.name);
+        let output = isolate((self.fun)(context, remainder), timeout).await;
+        dispatch.finish(context, info, output).await
+    }
+
+    fn dispatch(&self) -> Dispatch<'_, D, T, E> {
+        Dispatch {
+            kind: "Component handler",
+            name: self.name,
+            checks: &self.checks,
+            error_handler: self.error_handler.as_ref(),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::cooldown::{Bucket, BucketKind};
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// What happens when a command is used while it has reached its [concurrency limit](Concurrency).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConcurrencyMode {
+    /// The execution is rejected and the user is told the command is already running.
+    Reject,
+    /// The execution waits until a running one finishes, executing in the order the command was
+    /// used.
+    Queue,
+}
+
+/// A limit of executions of a command running at the same time for every [bucket](BucketKind).
+///
+/// Queued executions can take longer than the three seconds discord waits for the initial
+/// response, so the interaction is always deferred before waiting, ephemerally if the
+/// [defer policy](crate::defer::Defer) of the command says so.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The amount of executions allowed to run at the same time.
+    pub limit: u32,
+    /// The scope the limit applies to.
+    pub bucket: BucketKind,
+    /// What happens when the limit is reached.
+    pub mode: ConcurrencyMode,
+}
+
+impl Concurrency {
+    /// Creates a new limit allowing `limit` executions to run at the same time, rejecting the
+    /// executions once the limit is reached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn new(limit: u32, bucket: BucketKind) -> Self {
+        assert!(limit > 0, "A concurrency limit must allow at least one execution");
+        Self {
+            limit,
+            bucket,
+            mode: ConcurrencyMode::Reject,
+        }
+    }
+
+    /// Queues the executions once the limit is reached instead of rejecting them.
+    pub fn queue(mut self) -> Self {
+        self.mode = ConcurrencyMode::Queue;
+        self
+    }
+}
+
+type Semaphores = Arc<Mutex<HashMap<(String, Bucket), Arc<Semaphore>>>>;
+
+/// A permission to run an execution, which frees its slot when dropped.
+pub struct ConcurrencyPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    key: (String, Bucket),
+    semaphores: Semaphores,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+
+        // Drop the semaphore once no execution is running or queued, so the map doesn't grow
+        // forever.
+        let mut lock = self.semaphores.lock();
+        if lock.get(&self.key).is_some_and(|semaphore| Arc::strong_count(semaphore) == 1) {
+            lock.remove(&self.key);
+        }
+    }
+}
+
+/// Keeps track of the executions running for every command and [bucket](Bucket).
+#[derive(Default)]
+pub struct ConcurrencyLimiter {
+    semaphores: Semaphores,
+}
+
+impl ConcurrencyLimiter {
+    /// Creates a new limiter without running executions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts an execution of the given command if the limit allows it, returning `None` if the
+    /// limit has been reached or other executions are queued.
+    pub fn try_acquire(&self, command: &str, bucket: Bucket, concurrency: &Concurrency) -> Option<ConcurrencyPermit> {
+        let key = (command.to_string(), bucket);
+        let permit = self.semaphore(&key, concurrency).try_acquire_owned().ok()?;
+
+        Some(self.permit(key, permit))
+    }
+
+    /// Waits until an execution of the given command can start, in the order this method was
+    /// called.
+    pub async fn acquire(&self, command: &str, bucket: Bucket, concurrency: &Concurrency) -> ConcurrencyPermit {
+        let key = (command.to_string(), bucket);
+        let permit = self
+            .semaphore(&key, concurrency)
+            .acquire_owned()
+            .await
+            .expect("Concurrency semaphores are never closed");
+
+        self.permit(key, permit)
+    }
+
+    fn semaphore(&self, key: &(String, Bucket), concurrency: &Concurrency) -> Arc<Semaphore> {
+        let mut lock = self.semaphores.lock();
+        let semaphore = lock
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(Semaphore::new(concurrency.limit as usize)));
+
+        Arc::clone(semaphore)
+    }
+
+    fn permit(&self, key: (String, Bucket), permit: OwnedSemaphorePermit) -> ConcurrencyPermit {
+        ConcurrencyPermit {
+            permit: Some(permit),
+            key,
+            semaphores: Arc::clone(&self.semaphores),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_idle_semaphores() {
+        let limiter = ConcurrencyLimiter::new();
+        let concurrency = Concurrency::new(1, BucketKind::Global);
+
+        let permit = limiter.try_acquire("play", Bucket::Global, &concurrency).unwrap();
+        assert!(limiter.try_acquire("play", Bucket::Global, &concurrency).is_none());
+        assert_eq!(limiter.semaphores.lock().len(), 1);
+
+        drop(permit);
+        assert!(limiter.semaphores.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn keeps_semaphores_with_queued_executions() {
+        let limiter = Arc::new(ConcurrencyLimiter::new());
+        let concurrency = Concurrency::new(1, BucketKind::Global).queue();
+
+        let permit = limiter.acquire("play", Bucket::Global, &concurrency).await;
+        let queued = tokio::spawn({
+            let limiter = Arc::clone(&limiter);
+            async move { limiter.acquire("play", Bucket::Global, &concurrency).await }
+        });
+        tokio::task::yield_now().await;
+
+        drop(permit);
+        assert_eq!(limiter.semaphores.lock().len(), 1);
+
+        drop(queued.await.unwrap());
+        assert!(limiter.semaphores.lock().is_empty());
+    }
+}
@@ -1,4 +1,6 @@
 use parking_lot::Mutex;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{oneshot::Sender, Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 use twilight_model::channel::message::MessageFlags;
 use crate::{
     builder::WrappedClient,
@@ -6,7 +8,13 @@ use crate::{
     wait::{InteractionWaiter, WaiterWaker}
 };
 
-use crate::modal::{Modal, WaitModal};
+use crate::argument::ParsedArgument;
+use crate::collector::ComponentCollectorBuilder;
+use crate::custom_id::{CustomId, CustomIdCodec, CustomIdError};
+use crate::extensions::Extensions;
+use crate::modal::{Modal, ModalError, WaitModal};
+use crate::paginator::{PageSource, Paginator};
+use crate::response::{Reply, ResponseError, ResponseState};
 use crate::wait::new_pair;
 
 /// The value the user is providing to the argument.
@@ -16,6 +24,94 @@ pub struct Focused {
     pub kind: CommandOptionType,
 }
 
+/// The slot used to deliver the initial response of an interaction through a channel instead of
+/// using discord's http api. This is used when interactions are received through an http endpoint,
+/// where the initial response must be sent back as the body of the request.
+///
+/// It also keeps the [response state](ResponseState) of the interaction, since the endpoint may
+/// defer the interaction on behalf of the command.
+#[derive(Clone, Default)]
+pub(crate) struct InitialResponder {
+    slot: Arc<Mutex<ResponderSlot>>,
+    /// Held while a response is being sent, so responses sent at the same time, like the automatic
+    /// defer and a reply of the command, see the state left by the previous one.
+    responding: Arc<AsyncMutex<()>>,
+}
+
+#[derive(Default)]
+struct ResponderSlot {
+    sender: Option<Sender<InteractionResponse>>,
+    state: ResponseState,
+    /// Whether the endpoint deferred the interaction and the command did not respond it yet.
+    deferred_by_endpoint: bool,
+}
+
+impl InitialResponder {
+    /// Creates a new responder delivering the response through the given sender.
+    #[cfg_attr(not(feature = "endpoint"), allow(dead_code))]
+    pub(crate) fn new(sender: Sender<InteractionResponse>) -> Self {
+        Self {
+            slot: Arc::new(Mutex::new(ResponderSlot {
+                sender: Some(sender),
+                ..Default::default()
+            })),
+            responding: Default::default(),
+        }
+    }
+
+    /// Waits until no other response of the interaction is being sent, keeping the others
+    /// waiting until the returned guard is dropped.
+    async fn lock(&self) -> AsyncMutexGuard<'_, ()> {
+        self.responding.lock().await
+    }
+
+    /// Tries to deliver the response through the channel, returning `false` if the response
+    /// must be sent using the http api instead.
+    pub(crate) fn deliver(&self, response: &InteractionResponse) -> bool {
+        let mut slot = self.slot.lock();
+        let Some(sender) = slot.sender.take() else {
+            return false;
+        };
+
+        let delivered = sender.send(response.clone()).is_ok();
+        if delivered {
+            slot.state = ResponseState::after(response.kind);
+        }
+
+        delivered
+    }
+
+    /// Marks the interaction as deferred by the endpoint, returning `false` if the initial
+    /// response was already delivered.
+    #[cfg_attr(not(feature = "endpoint"), allow(dead_code))]
+    pub(crate) fn defer_on_behalf(&self) -> bool {
+        let mut slot = self.slot.lock();
+
+        if slot.sender.take().is_none() {
+            return false;
+        }
+
+        slot.state = ResponseState::Deferred;
+        slot.deferred_by_endpoint = true;
+        true
+    }
+
+    /// Returns whether the endpoint deferred the interaction, clearing the flag.
+    fn take_deferred_by_endpoint(&self) -> bool {
+        std::mem::take(&mut self.slot.lock().deferred_by_endpoint)
+    }
+
+    fn state(&self) -> ResponseState {
+        self.slot.lock().state
+    }
+
+    fn set_state(&self, state: ResponseState) {
+        let mut slot = self.slot.lock();
+        slot.state = state;
+        slot.deferred_by_endpoint = false;
+    }
+}
+
 /// Context given to all functions used to autocomplete arguments.
 pub struct AutocompleteContext<'a, D> {
     /// The http client used by the framework.
@@ -61,9 +157,17 @@ pub struct SlashContext<'a, D> {
     /// The data shared across the framework.
     pub data: &'a D,
     /// Components waiting for an interaction.
-    pub waiters: &'a Mutex<Vec<WaiterWaker>>,
+    pub waiters: &'a Arc<Mutex<Vec<WaiterWaker>>>,
+    /// The codec used to encode and decode typed custom ids.
+    pub custom_ids: &'a CustomIdCodec,
     /// The interaction itself.
     pub interaction: Interaction,
+    /// Data passed to the command by [layers](crate::middleware::Layer) and hooks.
+    pub extensions: Extensions,
+    pub(crate) responder: InitialResponder,
+    /// The arguments of the command once parsed, shared with the [info](crate::command::CommandInfo)
+    /// of the invocation.
+    pub(crate) arguments: Arc<OnceLock<Vec<ParsedArgument>>>,
 }
 
 impl<'a, D> Clone for SlashContext<'a, D> {
@@ -74,7 +178,11 @@ impl<'a, D> Clone for SlashContext<'a, D> {
             interaction_client: self.http_client.inner().interaction(self.application_id),
             data: self.data,
             waiters: self.waiters,
+            custom_ids: self.custom_ids,
             interaction: self.interaction.clone(),
+            extensions: self.extensions.clone(),
+            responder: self.responder.clone(),
+            arguments: Arc::clone(&self.arguments),
         }
     }
 }
@@ -85,8 +193,10 @@ impl<'a, D> SlashContext<'a, D> {
         http_client: &'a WrappedClient,
         application_id: Id<ApplicationMarker>,
         data: &'a D,
-        waiters: &'a Mutex<Vec<WaiterWaker>>,
+        waiters: &'a Arc<Mutex<Vec<WaiterWaker>>>,
+        custom_ids: &'a CustomIdCodec,
         interaction: Interaction,
+        responder: InitialResponder,
     ) -> Self {
         let interaction_client = http_client.inner().interaction(application_id);
         Self {
@@ -95,7 +205,11 @@ impl<'a, D> SlashContext<'a, D> {
             interaction_client,
             data,
             waiters,
+            custom_ids,
             interaction,
+            extensions: Extensions::new(),
+            responder,
+            arguments: Default::default(),
         }
     }
 
@@ -104,13 +218,84 @@ impl<'a, D> SlashContext<'a, D> {
         self.http_client.inner()
     }
 
+    /// Records the parsed arguments of the command, called by the command macro once all of them
+    /// have been parsed. Only the first recording is kept.
+    #[doc(hidden)]
+    pub fn record_arguments(&self, arguments: Vec<ParsedArgument>) {
+        let _ = self.arguments.set(arguments);
+    }
+
     /// Gets a mutable reference to the [interaction](Interaction) owned by the context.
     #[deprecated(since = "0.12.0", note = "Use the `interaction` field directly with a mutable context")]
     pub fn interaction_mut(&mut self) -> &mut Interaction {
         &mut self.interaction
     }
 
-    /// Defers the interaction, allowing to respond later.
+    /// Gets the current [state](ResponseState) of the response of the interaction.
+    pub fn response_state(&self) -> ResponseState {
+        self.responder.state()
+    }
+
+    fn set_response_state(&self, state: ResponseState) {
+        self.responder.set_state(state);
+    }
+
+    /// Sends the initial response of the interaction.
+    ///
+    /// Prefer this method over using the [interaction client](InteractionClient) directly, as
+    /// when interactions are received through an http endpoint, the initial response is sent back
+    /// as the body of the http request instead of using discord's http api. This also keeps track
+    /// of the [response state](Self::response_state) of the interaction.
+    pub async fn create_response(&self, response: &InteractionResponse) -> Result<(), twilight_http::Error> {
+        let _responding = self.responder.lock().await;
+        self.send_response(response).await
+    }
+
+    async fn send_response(&self, response: &InteractionResponse) -> Result<(), twilight_http::Error> {
+        if self.responder.deliver(response) {
+            return Ok(());
+        }
+
+        if self.responder.take_deferred_by_endpoint() {
+            // The endpoint deferred the interaction while the command was running, so the
+            // response replaces the deferred one instead.
+            return self.replace_deferred(response).await;
+        }
+
+        self.interaction_client
+            .create_response(self.interaction.id, &self.interaction.token, response)
+            .await?;
+
+        self.set_response_state(ResponseState::after(response.kind));
+
+        Ok(())
+    }
+
+    async fn replace_deferred(&self, response: &InteractionResponse) -> Result<(), twilight_http::Error> {
+        let data = match (response.kind, &response.data) {
+            (
+                InteractionResponseType::ChannelMessageWithSource
+                | InteractionResponseType::UpdateMessage,
+                Some(data),
+            ) => data,
+            // Deferring again is not needed, and other responses can't replace a deferred one.
+            _ => return Ok(()),
+        };
+
+        self.interaction_client
+            .update_response(&self.interaction.token)
+            .content(data.content.as_deref())
+            .embeds(data.embeds.as_deref())
+            .components(data.components.as_deref())
+            .await?;
+
+        self.set_response_state(ResponseState::Responded);
+
+        Ok(())
+    }
+
+    /// Defers the interaction, allowing to respond later. Does nothing if the interaction has
+    /// already been acknowledged.
     ///
     /// # Examples
     ///
@@ -125,37 +310,168 @@ impl<'a, D> SlashContext<'a, D> {
     ///
     ///     // Do something here
     ///
-    ///     // Now edit the interaction
-    ///     ctx.interaction_client.update_response(&ctx.interaction.token)
-    ///         .content(Some("Hello world"))
-    ///         .unwrap()
-    ///         .await?;
+    ///     // Now respond the interaction, which edits the deferred response
+    ///     ctx.reply("Hello world").await?;
     ///
     ///     Ok(())
     /// }
     /// ```
     pub async fn defer(&self, ephemeral: bool) -> Result<(), twilight_http::Error> {
+        let _responding = self.responder.lock().await;
+        if self.response_state().is_acknowledged() {
+            return Ok(());
+        }
+
+        self.send_response(&InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: if ephemeral {
+                Some(InteractionResponseData {
+                    flags: Some(MessageFlags::EPHEMERAL),
+                    ..Default::default()
+                })
+            } else {
+                None
+            },
+        })
+        .await
+    }
+
+    /// Responds the interaction, using the right endpoint for the current
+    /// [response state](Self::response_state):
+    ///
+    /// - If the interaction has not been answered, the reply is sent as the initial response.
+    /// - If the interaction has been deferred, the deferred response is edited. The visibility of
+    ///   the message was already decided when deferring, so the [ephemeral](Reply::ephemeral)
+    ///   flag is ignored.
+    /// - If the interaction has been responded, the reply is sent as a followup message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use vesper::prelude::*;
+    ///
+    /// #[command]
+    /// #[description = "My command description"]
+    /// async fn my_command(ctx: &SlashContext<()>) -> DefaultCommandResult {
+    ///     ctx.reply("Hello world").await?;
+    ///     // The interaction has been responded, so this is sent as a followup message.
+    ///     ctx.reply_ephemeral("Only you can see this").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn reply(&self, reply: impl Into<Reply>) -> Result<(), ResponseError> {
+        let reply = reply.into();
+        let _responding = self.responder.lock().await;
+
+        match self.response_state() {
+            ResponseState::Unanswered => {
+                self.send_response(&InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(reply.into_data()),
+                })
+                .await?;
+            }
+            ResponseState::Deferred => {
+                self.update_original(&reply).await?;
+                self.set_response_state(ResponseState::Responded);
+            }
+            ResponseState::Responded => {
+                self.send_followup(&reply).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Responds the interaction with an ephemeral message, see [reply](Self::reply).
+    pub async fn reply_ephemeral(&self, reply: impl Into<Reply>) -> Result<(), ResponseError> {
+        self.reply(reply.into().ephemeral()).await
+    }
+
+    /// Replaces the original response of the interaction with the given message.
+    ///
+    /// If the interaction has not been answered, this is only possible for message components,
+    /// in which case the message containing the component is updated, otherwise
+    /// [NotResponded](ResponseError::NotResponded) is returned.
+    pub async fn edit(&self, reply: impl Into<Reply>) -> Result<(), ResponseError> {
+        let reply = reply.into();
+        let _responding = self.responder.lock().await;
+
+        match self.response_state() {
+            ResponseState::Unanswered if self.interaction.message.is_some() => {
+                self.send_response(&InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(reply.into_data()),
+                })
+                .await?;
+            }
+            ResponseState::Unanswered => return Err(ResponseError::NotResponded),
+            _ => {
+                self.update_original(&reply).await?;
+                self.set_response_state(ResponseState::Responded);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends a followup message, returning the created message. The interaction must have been
+    /// responded or deferred first.
+    pub async fn followup(&self, reply: impl Into<Reply>) -> Result<Message, ResponseError> {
+        let _responding = self.responder.lock().await;
+        if !self.response_state().is_acknowledged() {
+            return Err(ResponseError::NotResponded);
+        }
+
+        let message = self.send_followup(&reply.into()).await?;
+        self.set_response_state(ResponseState::Responded);
+
+        Ok(message)
+    }
+
+    /// Deletes the original response of the interaction.
+    pub async fn delete(&self) -> Result<(), ResponseError> {
+        if !self.response_state().is_acknowledged() {
+            return Err(ResponseError::NotResponded);
+        }
+
         self.interaction_client
-            .create_response(
-                self.interaction.id,
-                &self.interaction.token,
-                &InteractionResponse {
-                    kind: InteractionResponseType::DeferredChannelMessageWithSource,
-                    data: if ephemeral {
-                        Some(InteractionResponseData {
-                            flags: Some(MessageFlags::EPHEMERAL),
-                            ..Default::default()
-                        })
-                    } else {
-                        None
-                    },
-                },
-            )
+            .delete_response(&self.interaction.token)
             .await?;
 
         Ok(())
     }
 
+    async fn update_original(&self, reply: &Reply) -> Result<(), ResponseError> {
+        self.interaction_client
+            .update_response(&self.interaction.token)
+            .content(reply.content.as_deref())
+            .embeds(Some(&reply.embeds))
+            .components(Some(&reply.components))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn send_followup(&self, reply: &Reply) -> Result<Message, ResponseError> {
+        let mut request = self
+            .interaction_client
+            .create_followup(&self.interaction.token)
+            .embeds(&reply.embeds)
+            .components(&reply.components);
+
+        if let Some(content) = &reply.content {
+            request = request.content(content);
+        }
+
+        if let Some(flags) = reply.flags() {
+            request = request.flags(flags);
+        }
+
+        Ok(request.await?.model().await?)
+    }
+
     /// Creates a modal that will be prompted to the user in discord, returning a [`WaitModal`] that
     /// can be `.await`ed to retrieve the user input. If the returned [`WaitModal`] is not awaited,
     /// the modal will not close when submitted and the user won't be able to submit the modal.
@@ -186,16 +502,12 @@ impl<'a, D> SlashContext<'a, D> {
     /// ```
     ///
     /// [`WaitModal`]: WaitModal
-    pub async fn create_modal<M>(&self) -> Result<WaitModal<M>, twilight_http::Error>
+    pub async fn create_modal<M>(&self) -> Result<WaitModal<'_, M>, twilight_http::Error>
     where
         M: Modal<D>
     {
         let modal_id = self.interaction.id.to_string();
-        self.interaction_client.create_response(
-            self.interaction.id,
-            &self.interaction.token,
-            &M::create(self, modal_id.clone())
-        ).await?;
+        self.create_response(&M::create(self, modal_id.clone())).await?;
 
         let waiter = self.wait_interaction(move |interaction| {
             let Some(InteractionData::ModalSubmit(data)) = &interaction.data else {
@@ -208,13 +520,44 @@ impl<'a, D> SlashContext<'a, D> {
         Ok(WaitModal::new(waiter, &self.interaction_client, M::parse))
     }
 
+    /// Encodes the given state into a custom id, using the [codec](CustomIdCodec) of the framework.
+    pub fn custom_id<S: CustomId>(&self, state: &S) -> Result<String, CustomIdError> {
+        self.custom_ids.encode(state)
+    }
+
+    /// Creates a modal using the given state as its custom id, without waiting for it to be
+    /// submitted. The submission is routed to the [modal handler](crate::component::ComponentHandler)
+    /// of the state.
+    pub async fn show_modal<M, S>(&self, state: &S) -> Result<(), ModalError>
+    where
+        M: Modal<D>,
+        S: CustomId
+    {
+        let custom_id = self.custom_ids.encode(state)?;
+        self.create_response(&M::create(self, custom_id)).await?;
+
+        Ok(())
+    }
+
+    /// Returns a builder of a [collector](crate::collector::ComponentCollector), used to receive
+    /// every message component interaction matching the configured filters as a stream.
+    pub fn collect_components(&self) -> ComponentCollectorBuilder<'_> {
+        ComponentCollectorBuilder::new(self)
+    }
+
+    /// Returns a [paginator](Paginator) showing the given pages, which must be
+    /// [run](Paginator::run) to respond the interaction.
+    pub fn paginate<'ctx>(&'ctx self, source: impl PageSource + 'ctx) -> Paginator<'ctx, 'a, D> {
+        Paginator::new(self, source)
+    }
+
     /// Returns a waiter used to wait for a specific interaction which satisfies the provided
     /// closure.
     pub fn wait_interaction<F>(&self, fun: F) -> InteractionWaiter
     where
         F: Fn(&Interaction) -> bool + Send + 'static
     {
-        let (waker, waiter) = new_pair(fun);
+        let (waker, waiter) = new_pair(self.waiters, fun);
         let mut lock = self.waiters.lock();
         lock.push(waker);
         waiter
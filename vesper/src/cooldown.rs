@@ -0,0 +1,241 @@
+use crate::twilight_exports::{ChannelMarker, GuildMarker, Id, Interaction, UserMarker};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::{Duration, Instant},
+};
+
+/// The scope a [cooldown](Cooldown) applies to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BucketKind {
+    /// Each user has its own cooldown, shared across all guilds.
+    User,
+    /// Each member has its own cooldown in every guild, outside guilds it behaves like [User](Self::User).
+    Member,
+    /// Each channel has its own cooldown.
+    Channel,
+    /// Each guild has its own cooldown, outside guilds it behaves like [User](Self::User).
+    Guild,
+    /// The cooldown is shared by everyone.
+    Global,
+}
+
+/// The bucket an invocation falls in, resolved from a [bucket kind](BucketKind) and an interaction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Bucket {
+    User(Id<UserMarker>),
+    Member(Id<GuildMarker>, Id<UserMarker>),
+    Channel(Id<ChannelMarker>),
+    Guild(Id<GuildMarker>),
+    Global,
+}
+
+impl Bucket {
+    /// Resolves the bucket the given interaction falls in.
+    pub fn new(kind: BucketKind, interaction: &Interaction) -> Self {
+        let user = interaction.author_id();
+
+        match (kind, interaction.guild_id, user) {
+            (BucketKind::Global, ..) => Self::Global,
+            (BucketKind::Guild, Some(guild), _) => Self::Guild(guild),
+            (BucketKind::Member, Some(guild), Some(user)) => Self::Member(guild, user),
+            (BucketKind::Channel, ..) => match interaction.channel.as_ref() {
+                Some(channel) => Self::Channel(channel.id),
+                None => user.map(Self::User).unwrap_or(Self::Global),
+            },
+            (_, _, Some(user)) => Self::User(user),
+            _ => Self::Global,
+        }
+    }
+}
+
+impl Display for Bucket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::User(user) => write!(f, "user:{}", user),
+            Self::Member(guild, user) => write!(f, "member:{}:{}", guild, user),
+            Self::Channel(channel) => write!(f, "channel:{}", channel),
+            Self::Guild(guild) => write!(f, "guild:{}", guild),
+            Self::Global => f.write_str("global"),
+        }
+    }
+}
+
+/// The key used to store the uses of a command inside a [bucket](Bucket).
+///
+/// Its [Display] representation is stable, so it can be used as a key in external storages.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CooldownKey {
+    /// The full path of the command, including its parent and group, separated by spaces.
+    pub command: String,
+    /// The bucket of the invocation.
+    pub bucket: Bucket,
+}
+
+impl Display for CooldownKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}:{}", self.command, self.bucket)
+    }
+}
+
+/// A cooldown applied to a command, allowing it to be used a limited amount of times in the
+/// given period for every [bucket](BucketKind).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cooldown {
+    /// The amount of times the command can be used in the given period.
+    pub uses: u32,
+    /// The period of time the uses are counted in.
+    pub duration: Duration,
+    /// The scope the cooldown applies to.
+    pub bucket: BucketKind,
+}
+
+impl Cooldown {
+    /// Creates a new cooldown allowing the command to be used `uses` times every `duration`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `uses` is zero.
+    pub fn new(uses: u32, duration: Duration, bucket: BucketKind) -> Self {
+        assert!(uses > 0, "A cooldown must allow at least one use");
+        Self {
+            uses,
+            duration,
+            bucket,
+        }
+    }
+}
+
+/// A storage used to keep track of command uses.
+///
+/// The framework uses an [in-memory storage](MemoryCooldownStorage) by default, this trait can be
+/// implemented to share cooldowns across multiple processes, for example using redis.
+#[async_trait]
+pub trait CooldownStorage: Send + Sync {
+    /// Registers a use of the given key if the [cooldown](Cooldown) allows it, returning `None`.
+    /// If the limit of uses has been reached, the use is not registered and the remaining time
+    /// until the command can be used again is returned.
+    ///
+    /// Implementations must perform both the check and the registration atomically.
+    async fn hit(&self, key: &CooldownKey, cooldown: &Cooldown) -> Option<Duration>;
+}
+
+/// How often the [memory storage](MemoryCooldownStorage) drops the keys which uses have expired.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The uses registered for a single [key](CooldownKey).
+struct Uses {
+    duration: Duration,
+    timestamps: VecDeque<Instant>,
+}
+
+impl Uses {
+    fn expired(&self, now: Instant) -> bool {
+        self.timestamps
+            .back()
+            .map(|last| now.duration_since(*last) >= self.duration)
+            .unwrap_or(true)
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    uses: HashMap<CooldownKey, Uses>,
+    last_sweep: Option<Instant>,
+}
+
+/// The default [cooldown storage](CooldownStorage), keeping track of uses in memory.
+#[derive(Default)]
+pub struct MemoryCooldownStorage {
+    entries: Mutex<Entries>,
+}
+
+impl MemoryCooldownStorage {
+    /// Creates a new, empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CooldownStorage for MemoryCooldownStorage {
+    async fn hit(&self, key: &CooldownKey, cooldown: &Cooldown) -> Option<Duration> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+
+        // Drop the keys which uses have all expired from time to time, so the map doesn't grow
+        // forever.
+        if entries.last_sweep.is_none_or(|last| now.duration_since(last) >= SWEEP_INTERVAL) {
+            entries.uses.retain(|_, uses| !uses.expired(now));
+            entries.last_sweep = Some(now);
+        }
+
+        let uses = entries.uses.entry(key.clone()).or_insert_with(|| Uses {
+            duration: cooldown.duration,
+            timestamps: VecDeque::new(),
+        });
+        uses.duration = cooldown.duration;
+
+        while uses
+            .timestamps
+            .front()
+            .map(|used| now.duration_since(*used) >= cooldown.duration)
+            .unwrap_or(false)
+        {
+            uses.timestamps.pop_front();
+        }
+
+        if uses.timestamps.len() >= cooldown.uses as usize {
+            let oldest = *uses.timestamps.front()?;
+            return Some(cooldown.duration - now.duration_since(oldest));
+        }
+
+        uses.timestamps.push_back(now);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(command: &str) -> CooldownKey {
+        CooldownKey {
+            command: command.to_string(),
+            bucket: Bucket::Global,
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_the_uses_in_the_period() {
+        let storage = MemoryCooldownStorage::new();
+        let cooldown = Cooldown::new(2, Duration::from_secs(60), BucketKind::Global);
+
+        assert_eq!(storage.hit(&key("ping"), &cooldown).await, None);
+        assert_eq!(storage.hit(&key("ping"), &cooldown).await, None);
+
+        let remaining = storage.hit(&key("ping"), &cooldown).await.unwrap();
+        assert!(remaining <= cooldown.duration);
+
+        assert_eq!(storage.hit(&key("pong"), &cooldown).await, None);
+    }
+
+    #[tokio::test]
+    async fn sweeps_expired_keys_periodically() {
+        let storage = MemoryCooldownStorage::new();
+        let cooldown = Cooldown::new(1, Duration::from_millis(10), BucketKind::Global);
+
+        storage.hit(&key("ping"), &cooldown).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // The last sweep was too recent, so the expired key is kept.
+        storage.hit(&key("pong"), &cooldown).await;
+        assert_eq!(storage.entries.lock().uses.len(), 2);
+
+        storage.entries.lock().last_sweep = Some(Instant::now() - SWEEP_INTERVAL);
+        storage.hit(&key("pong"), &cooldown).await;
+        assert_eq!(storage.entries.lock().uses.len(), 1);
+    }
+}
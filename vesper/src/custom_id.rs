@@ -0,0 +1,501 @@
+use crate::twilight_exports::{Id, Interaction, InteractionData};
+#[cfg(feature = "signed-custom-id")]
+use hmac::{Hmac, Mac};
+#[cfg(feature = "signed-custom-id")]
+use sha2::Sha256;
+#[cfg(feature = "signed-custom-id")]
+use std::fmt::Write;
+use std::str::Split;
+use thiserror::Error;
+
+/// The maximum length of a custom id allowed by discord, in characters.
+pub const MAX_LENGTH: usize = 100;
+
+/// The separator used between the fields of a custom id.
+const SEPARATOR: char = ':';
+
+/// The amount of bytes of the HMAC kept in signed custom ids.
+#[cfg(feature = "signed-custom-id")]
+const SIGNATURE_LENGTH: usize = 8;
+
+#[cfg(feature = "signed-custom-id")]
+type HmacSha256 = Hmac<Sha256>;
+
+/// Errors that can occur when encoding or decoding custom ids.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum CustomIdError {
+    /// The encoded custom id exceeds the maximum length allowed by discord.
+    #[error("The custom id is {0} characters long, the maximum is 100")]
+    TooLong(usize),
+    /// The custom id does not belong to the expected namespace.
+    #[error("The custom id does not belong to the {0} namespace")]
+    Namespace(&'static str),
+    /// The signature of the custom id is missing or does not match its content.
+    #[error("The custom id signature is missing or invalid")]
+    InvalidSignature,
+    /// The custom id has less fields than expected.
+    #[error("The custom id is missing fields")]
+    MissingField,
+    /// The custom id has more fields than expected.
+    #[error("The custom id has more fields than expected")]
+    TrailingFields,
+    /// A field could not be decoded.
+    #[error("Failed to decode custom id field: {0}")]
+    InvalidField(String),
+    /// The interaction is not a component or modal submit interaction.
+    #[error("The interaction does not have a custom id")]
+    NotComponent,
+}
+
+/// A value that can be stored as a field of a [custom id](CustomId).
+pub trait CustomIdField: Sized {
+    /// Encodes the value, the output must not contain `:`.
+    fn encode(&self) -> String;
+    /// Decodes the value from its encoded representation.
+    fn decode(raw: &str) -> Result<Self, CustomIdError>;
+}
+
+/// A typed state that can be stored in the custom id of a component or modal.
+///
+/// This trait is normally implemented using the derive macro, refer to it to see full
+/// documentation about its usage and attributes.
+pub trait CustomId: Sized {
+    /// The namespace the custom ids of this state belong to.
+    const NAMESPACE: &'static str;
+    /// The prefix of the custom ids of this state, this is, the namespace followed by `:`.
+    const PREFIX: &'static str;
+
+    /// Writes all the fields of the state.
+    fn write(&self, writer: &mut CustomIdWriter);
+    /// Reads all the fields of the state.
+    fn read(reader: &mut CustomIdReader<'_>) -> Result<Self, CustomIdError>;
+}
+
+/// A writer used to encode the fields of a [custom id](CustomId).
+pub struct CustomIdWriter {
+    output: String,
+    written: usize,
+}
+
+impl CustomIdWriter {
+    /// Writes the given field.
+    pub fn write<T: CustomIdField>(&mut self, value: &T) {
+        if self.written > 0 {
+            self.output.push(SEPARATOR);
+        }
+
+        self.output.push_str(&value.encode());
+        self.written += 1;
+    }
+}
+
+/// A reader used to decode the fields of a [custom id](CustomId).
+pub struct CustomIdReader<'a> {
+    fields: Split<'a, char>,
+    read: usize,
+}
+
+impl CustomIdReader<'_> {
+    /// Reads the next field, which must be encoded exactly as [encode](CustomIdField::encode)
+    /// would, so every state has a single valid custom id.
+    pub fn read<T: CustomIdField>(&mut self) -> Result<T, CustomIdError> {
+        let raw = self.fields.next().ok_or(CustomIdError::MissingField)?;
+        let value = T::decode(raw)?;
+
+        if value.encode() != raw {
+            return Err(invalid(raw));
+        }
+
+        self.read += 1;
+        Ok(value)
+    }
+}
+
+/// The codec used to encode typed states into custom ids and decode them back.
+///
+/// Custom ids are encoded as the [prefix](CustomId::PREFIX) of the state followed by its
+/// fields, separated by `:`, so even states without fields end with the separator. When a key is
+/// provided, a truncated HMAC-SHA256 signature of the custom id is appended, so users can't forge
+/// the state of the component. Signing custom ids requires the `signed-custom-id` feature.
+#[derive(Default)]
+pub struct CustomIdCodec {
+    #[cfg(feature = "signed-custom-id")]
+    key: Option<Vec<u8>>,
+}
+
+impl CustomIdCodec {
+    /// Creates a new codec which does not sign custom ids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new codec signing custom ids with the given key.
+    #[cfg(feature = "signed-custom-id")]
+    pub fn signed(key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key: Some(key.into()),
+        }
+    }
+
+    /// Encodes the given state into a custom id.
+    pub fn encode<S: CustomId>(&self, state: &S) -> Result<String, CustomIdError> {
+        let mut writer = CustomIdWriter {
+            output: String::from(S::PREFIX),
+            written: 0,
+        };
+        state.write(&mut writer);
+
+        let output = self.sign(writer.output);
+
+        let length = output.chars().count();
+        if length > MAX_LENGTH {
+            return Err(CustomIdError::TooLong(length));
+        }
+
+        Ok(output)
+    }
+
+    /// Decodes the given custom id into the state, verifying its signature if the codec has a key.
+    pub fn decode<S: CustomId>(&self, custom_id: &str) -> Result<S, CustomIdError> {
+        let content = self.verify(custom_id)?;
+
+        let fields = content
+            .strip_prefix(S::PREFIX)
+            .ok_or(CustomIdError::Namespace(S::NAMESPACE))?;
+
+        let mut reader = CustomIdReader {
+            fields: fields.split(SEPARATOR),
+            read: 0,
+        };
+        let state = S::read(&mut reader)?;
+
+        match reader.fields.next() {
+            None => (),
+            // States without fields leave the empty remainder unread.
+            Some("") if reader.read == 0 && fields.is_empty() => (),
+            Some(_) => return Err(CustomIdError::TrailingFields),
+        }
+
+        Ok(state)
+    }
+
+    /// Decodes the custom id of the given component or modal submit interaction into the state.
+    pub fn decode_interaction<S: CustomId>(&self, interaction: &Interaction) -> Result<S, CustomIdError> {
+        match &interaction.data {
+            Some(InteractionData::MessageComponent(data)) => self.decode(&data.custom_id),
+            Some(InteractionData::ModalSubmit(data)) => self.decode(&data.custom_id),
+            _ => Err(CustomIdError::NotComponent),
+        }
+    }
+
+    /// Appends the signature to the given custom id if the codec has a key.
+    #[cfg(feature = "signed-custom-id")]
+    fn sign(&self, mut custom_id: String) -> String {
+        if let Some(mac) = self.mac(&custom_id) {
+            custom_id.push(SEPARATOR);
+            for byte in &mac.finalize().into_bytes()[..SIGNATURE_LENGTH] {
+                let _ = write!(custom_id, "{:02x}", byte);
+            }
+        }
+
+        custom_id
+    }
+
+    #[cfg(not(feature = "signed-custom-id"))]
+    fn sign(&self, custom_id: String) -> String {
+        custom_id
+    }
+
+    /// Verifies the signature of the given custom id if the codec has a key, returning its
+    /// content without the signature.
+    #[cfg(feature = "signed-custom-id")]
+    fn verify<'a>(&self, custom_id: &'a str) -> Result<&'a str, CustomIdError> {
+        if self.key.is_none() {
+            return Ok(custom_id);
+        }
+
+        let (content, signature) = custom_id
+            .rsplit_once(SEPARATOR)
+            .ok_or(CustomIdError::InvalidSignature)?;
+        let signature = decode_hex(signature).ok_or(CustomIdError::InvalidSignature)?;
+
+        self.mac(content)
+            .unwrap()
+            .verify_truncated_left(&signature)
+            .map_err(|_| CustomIdError::InvalidSignature)?;
+
+        Ok(content)
+    }
+
+    #[cfg(not(feature = "signed-custom-id"))]
+    fn verify<'a>(&self, custom_id: &'a str) -> Result<&'a str, CustomIdError> {
+        Ok(custom_id)
+    }
+
+    #[cfg(feature = "signed-custom-id")]
+    fn mac(&self, content: &str) -> Option<HmacSha256> {
+        let mut mac = HmacSha256::new_from_slice(self.key.as_ref()?)
+            .expect("HMAC accepts keys of any size");
+        mac.update(content.as_bytes());
+        Some(mac)
+    }
+}
+
+#[cfg(feature = "signed-custom-id")]
+fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    if input.len() != SIGNATURE_LENGTH * 2 {
+        return None;
+    }
+
+    (0..input.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(input.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn invalid(raw: &str) -> CustomIdError {
+    CustomIdError::InvalidField(raw.to_string())
+}
+
+/// Encodes the given number in base 36, which is shorter than its decimal representation.
+fn encode_radix(mut value: u64) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    if value == 0 {
+        return String::from("0");
+    }
+
+    let mut output = Vec::new();
+    while value > 0 {
+        output.push(DIGITS[(value % 36) as usize]);
+        value /= 36;
+    }
+
+    output.reverse();
+    String::from_utf8(output).unwrap()
+}
+
+macro_rules! unsigned_field {
+    ($($kind:ty),*) => {
+        $(
+            impl CustomIdField for $kind {
+                fn encode(&self) -> String {
+                    encode_radix(*self as u64)
+                }
+
+                fn decode(raw: &str) -> Result<Self, CustomIdError> {
+                    <$kind>::from_str_radix(raw, 36).map_err(|_| invalid(raw))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed_field {
+    ($($kind:ty),*) => {
+        $(
+            impl CustomIdField for $kind {
+                fn encode(&self) -> String {
+                    if *self < 0 {
+                        format!("-{}", encode_radix(self.unsigned_abs() as u64))
+                    } else {
+                        encode_radix(*self as u64)
+                    }
+                }
+
+                fn decode(raw: &str) -> Result<Self, CustomIdError> {
+                    <$kind>::from_str_radix(raw, 36).map_err(|_| invalid(raw))
+                }
+            }
+        )*
+    };
+}
+
+unsigned_field!(u8, u16, u32, u64, usize);
+signed_field!(i8, i16, i32, i64, isize);
+
+impl CustomIdField for bool {
+    fn encode(&self) -> String {
+        String::from(if *self { "1" } else { "0" })
+    }
+
+    fn decode(raw: &str) -> Result<Self, CustomIdError> {
+        match raw {
+            "1" => Ok(true),
+            "0" => Ok(false),
+            _ => Err(invalid(raw)),
+        }
+    }
+}
+
+impl CustomIdField for String {
+    fn encode(&self) -> String {
+        self.replace('%', "%25").replace(SEPARATOR, "%3A")
+    }
+
+    fn decode(raw: &str) -> Result<Self, CustomIdError> {
+        Ok(raw.replace("%3A", ":").replace("%25", "%"))
+    }
+}
+
+impl<T> CustomIdField for Id<T> {
+    fn encode(&self) -> String {
+        encode_radix(self.get())
+    }
+
+    fn decode(raw: &str) -> Result<Self, CustomIdError> {
+        u64::from_str_radix(raw, 36)
+            .ok()
+            .and_then(Id::new_checked)
+            .ok_or_else(|| invalid(raw))
+    }
+}
+
+impl<T: CustomIdField> CustomIdField for Option<T> {
+    fn encode(&self) -> String {
+        match self {
+            Some(value) => format!("+{}", value.encode()),
+            None => String::new(),
+        }
+    }
+
+    fn decode(raw: &str) -> Result<Self, CustomIdError> {
+        match raw.strip_prefix('+') {
+            Some(value) => T::decode(value).map(Some),
+            None if raw.is_empty() => Ok(None),
+            None => Err(invalid(raw)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Vote {
+        poll: u32,
+        option: i8,
+        note: Option<String>,
+    }
+
+    impl CustomId for Vote {
+        const NAMESPACE: &'static str = "vote";
+        const PREFIX: &'static str = "vote:";
+
+        fn write(&self, writer: &mut CustomIdWriter) {
+            writer.write(&self.poll);
+            writer.write(&self.option);
+            writer.write(&self.note);
+        }
+
+        fn read(reader: &mut CustomIdReader<'_>) -> Result<Self, CustomIdError> {
+            Ok(Self {
+                poll: reader.read()?,
+                option: reader.read()?,
+                note: reader.read()?,
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Refresh;
+
+    impl CustomId for Refresh {
+        const NAMESPACE: &'static str = "refresh";
+        const PREFIX: &'static str = "refresh:";
+
+        fn write(&self, _: &mut CustomIdWriter) {}
+
+        fn read(_: &mut CustomIdReader<'_>) -> Result<Self, CustomIdError> {
+            Ok(Self)
+        }
+    }
+
+    fn vote() -> Vote {
+        Vote {
+            poll: 1295,
+            option: -3,
+            note: Some(String::from("a:b%c")),
+        }
+    }
+
+    #[test]
+    fn round_trips_fields() {
+        let codec = CustomIdCodec::new();
+        let encoded = codec.encode(&vote()).unwrap();
+
+        assert_eq!(encoded, "vote:zz:-3:+a%3Ab%25c");
+        assert_eq!(codec.decode::<Vote>(&encoded).unwrap(), vote());
+    }
+
+    #[test]
+    fn states_without_fields_keep_the_separator() {
+        let codec = CustomIdCodec::new();
+        let encoded = codec.encode(&Refresh).unwrap();
+
+        assert_eq!(encoded, Refresh::PREFIX);
+        assert_eq!(codec.decode::<Refresh>(&encoded).unwrap(), Refresh);
+        assert!(codec.decode::<Refresh>("refresh").is_err());
+        assert!(matches!(
+            codec.decode::<Refresh>("refresh:1"),
+            Err(CustomIdError::TrailingFields)
+        ));
+    }
+
+    #[test]
+    fn limits_the_length_in_characters() {
+        let codec = CustomIdCodec::new();
+        let mut vote = vote();
+
+        vote.note = Some("é".repeat(80));
+        let encoded = codec.encode(&vote).unwrap();
+        assert!(encoded.len() > MAX_LENGTH);
+
+        vote.note = Some("é".repeat(90));
+        assert!(matches!(codec.encode(&vote), Err(CustomIdError::TooLong(102))));
+    }
+
+    #[test]
+    fn rejects_non_canonical_fields() {
+        let codec = CustomIdCodec::new();
+
+        for custom_id in ["vote:+zz:-3:", "vote:ZZ:-3:", "vote:0zz:-3:", "vote:zz:-0:", "vote:zz:3:+a%3ab"] {
+            assert!(
+                matches!(codec.decode::<Vote>(custom_id), Err(CustomIdError::InvalidField(_))),
+                "{} was accepted",
+                custom_id
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "signed-custom-id")]
+    fn signed_round_trip() {
+        let codec = CustomIdCodec::signed("secret");
+
+        let encoded = codec.encode(&vote()).unwrap();
+        assert_eq!(codec.decode::<Vote>(&encoded).unwrap(), vote());
+
+        let encoded = codec.encode(&Refresh).unwrap();
+        assert_eq!(codec.decode::<Refresh>(&encoded).unwrap(), Refresh);
+    }
+
+    #[test]
+    #[cfg(feature = "signed-custom-id")]
+    fn rejects_tampered_custom_ids() {
+        let codec = CustomIdCodec::signed("secret");
+        let encoded = codec.encode(&vote()).unwrap();
+        let (content, signature) = encoded.rsplit_once(SEPARATOR).unwrap();
+
+        let tampered = format!("{}{}", content.replacen("zz", "zy", 1), &encoded[content.len()..]);
+        assert!(matches!(codec.decode::<Vote>(&tampered), Err(CustomIdError::InvalidSignature)));
+
+        let other_key = CustomIdCodec::signed("other").encode(&vote()).unwrap();
+        assert!(matches!(codec.decode::<Vote>(&other_key), Err(CustomIdError::InvalidSignature)));
+
+        assert!(matches!(codec.decode::<Vote>(content), Err(CustomIdError::InvalidSignature)));
+        assert_eq!(signature.len(), SIGNATURE_LENGTH * 2);
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/// The delay used by [Defer::before_deadline], leaving some margin before the three seconds
+/// discord waits for the initial response.
+const DEADLINE_MARGIN: Duration = Duration::from_millis(2500);
+
+/// Describes when the framework automatically defers the interaction of a command.
+///
+/// When set for a command, using [Command::defer](crate::command::Command::defer) or the
+/// `#[defer]` attribute, the command is always deferred. When set as the
+/// [policy of the framework](crate::builder::FrameworkBuilder::auto_defer), it applies to all the
+/// commands not having their own.
+///
+/// The command runs until the delay elapses, and if the interaction has not been acknowledged
+/// by then, it is deferred while the command keeps running.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Defer {
+    /// The time to wait before deferring the interaction, if zero, the interaction is deferred
+    /// before the command runs.
+    pub delay: Duration,
+    /// Whether the deferred response is ephemeral.
+    pub ephemeral: bool,
+}
+
+impl Defer {
+    /// Defers the interaction before the command runs.
+    pub const fn immediate() -> Self {
+        Self::after(Duration::ZERO)
+    }
+
+    /// Defers the interaction if the command has not acknowledged it after the given delay.
+    pub const fn after(delay: Duration) -> Self {
+        Self {
+            delay,
+            ephemeral: false,
+        }
+    }
+
+    /// Defers the interaction if the command has not acknowledged it shortly before discord
+    /// stops waiting for the initial response.
+    pub const fn before_deadline() -> Self {
+        Self::after(DEADLINE_MARGIN)
+    }
+
+    /// Makes the deferred response ephemeral.
+    pub const fn ephemeral(mut self) -> Self {
+        self.ephemeral = true;
+        self
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    check::CheckOutcome,
+    command::{CommandInfo, ExecutionResult, ExecutionState, OutputLocation},
+    context::SlashContext,
+    error::FrameworkError,
+    hook::{CheckHook, ErrorHandlerHook},
+    unwind::{panic_message, CatchUnwind},
+};
+use std::{future::Future, time::Duration};
+use tracing::{debug, info, warn};
+
+/// The checks and error handler of something executed by the framework, either a
+/// [command](crate::command::Command) or a [component handler](crate::component::ComponentHandler),
+/// shared by both to run its checks and give its errors to the error handler.
+pub(crate) struct Dispatch<'a, D, T, E> {
+    /// What is being executed, used in the logs.
+    pub(crate) kind: &'static str,
+    pub(crate) name: &'static str,
+    pub(crate) checks: &'a [CheckHook<D, E>],
+    pub(crate) error_handler: Option<&'a ErrorHandlerHook<D, T, E>>,
+}
+
+impl<D, T, E> Dispatch<'_, D, T, E> {
+    /// Runs the checks in order, stopping at the first one not passing.
+    pub(crate) async fn run_checks<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+    ) -> Result<CheckOutcome, E> {
+        debug!("Running {} [{}] checks", self.kind, self.name);
+        for check in self.checks {
+            let outcome = (check.0)(context).await?;
+            if !outcome.is_passed() {
+                debug!("{} [{}] check denied the execution", self.kind, self.name);
+                return Ok(outcome);
+            }
+        }
+        debug!("All {} [{}] checks passed", self.kind, self.name);
+        Ok(CheckOutcome::Passed)
+    }
+
+    /// Runs the checks, returning the result of the execution if they did not pass.
+    pub(crate) async fn pass_checks<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        info: &CommandInfo<'_, D, T, E>,
+    ) -> Result<(), ExecutionResult<T, E>> {
+        let location;
+        let state;
+        let mut denial = None;
+
+        // Panics inside checks are given to the error handler like the ones of the execution.
+        let outcome = match CatchUnwind(Box::pin(self.run_checks(context))).await {
+            Ok(outcome) => outcome.map_err(FrameworkError::Check),
+            Err(payload) => Err(FrameworkError::Panicked(panic_message(&*payload))),
+        };
+
+        match outcome {
+            Ok(CheckOutcome::Passed) => return Ok(()),
+            Err(why) => {
+                state = match &why {
+                    FrameworkError::Panicked(message) => {
+                        warn!("{} [{}] check panicked: {}", self.kind, self.name, message);
+                        ExecutionState::Panicked
+                    }
+                    _ => ExecutionState::CheckErrored,
+                };
+                // If there is an error handler, execute it, if not, discard the error.
+                if let Some(hook) = self.error_handler {
+                    info!(
+                        "{} [{}] check raised an error, using established error handler",
+                        self.kind, self.name
+                    );
+                    (hook.0)(context, info, why).await;
+                    location = OutputLocation::TakenByErrorHandler;
+                } else {
+                    info!(
+                        "{} [{}] check raised an error, but no error handler was established",
+                        self.kind, self.name
+                    );
+                    location = OutputLocation::Present(Err(why));
+                }
+            }
+            Ok(CheckOutcome::Denied(reason)) => {
+                state = ExecutionState::CheckFailed;
+                location = OutputLocation::NotExecuted;
+                denial = Some(reason);
+            }
+        }
+
+        Err(ExecutionResult {
+            state,
+            output: location,
+            error_id: None,
+            denial,
+        })
+    }
+
+    /// Builds the result of the execution from its output, giving its errors to the error
+    /// handler.
+    pub(crate) async fn finish<'cx, 'data: 'cx>(
+        &self,
+        context: &'cx mut SlashContext<'data, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        output: Result<T, FrameworkError<E>>,
+    ) -> ExecutionResult<T, E> {
+        let state;
+        let location;
+
+        // Timeouts and panics are given to the error handler as errors of the execution.
+        let error_state = match &output {
+            Err(FrameworkError::TimedOut(timeout)) => ExecutionState::TimedOut(*timeout),
+            Err(FrameworkError::Panicked(message)) => {
+                warn!("{} [{}] panicked: {}", self.kind, self.name, message);
+                ExecutionState::Panicked
+            }
+            _ => ExecutionState::CommandErrored,
+        };
+
+        match (self.error_handler, output) {
+            (Some(hook), Err(why)) => {
+                info!(
+                    "{} [{}] raised an error, using established error handler",
+                    self.kind, self.name
+                );
+                state = error_state;
+                location = OutputLocation::TakenByErrorHandler;
+
+                (hook.0)(context, info, why).await;
+            }
+            (_, Ok(res)) => {
+                debug!("{} [{}] executed successfully", self.kind, self.name);
+                state = ExecutionState::CommandFinished;
+                location = OutputLocation::Present(Ok(res));
+            }
+            (_, Err(res)) => {
+                info!(
+                    "{} [{}] raised an error, but no error handler was established",
+                    self.kind, self.name
+                );
+                state = error_state;
+                location = OutputLocation::Present(Err(res));
+            }
+        };
+
+        ExecutionResult {
+            state,
+            output: location,
+            error_id: None,
+            denial: None,
+        }
+    }
+}
+
+/// Runs the given future catching its panics and stopping it once the timeout elapses, if any.
+pub(crate) async fn isolate<F, T, E>(future: F, timeout: Option<Duration>) -> Result<T, FrameworkError<E>>
+where
+    F: Future<Output = Result<T, FrameworkError<E>>>,
+{
+    let future = CatchUnwind(Box::pin(future));
+
+    let output = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| FrameworkError::TimedOut(timeout))?,
+        None => future.await,
+    };
+
+    match output {
+        Ok(output) => output,
+        Err(payload) => Err(FrameworkError::Panicked(panic_message(&*payload))),
+    }
+}
+
+/// Runs the whole execution of an invocation, including its hooks and error handlers, turning
+/// its panics into a [panicked](ExecutionState::Panicked) result instead of tearing down the task
+/// processing the interaction.
+pub(crate) async fn catch_panics<F, T, E>(kind: &str, name: &str, future: F) -> ExecutionResult<T, E>
+where
+    F: Future<Output = ExecutionResult<T, E>>,
+{
+    CatchUnwind(Box::pin(future)).await.unwrap_or_else(|payload| {
+        warn!("{} [{}] execution panicked: {}", kind, name, panic_message(&*payload));
+        ExecutionResult {
+            state: ExecutionState::Panicked,
+            output: OutputLocation::NotExecuted,
+            error_id: None,
+            denial: None,
+        }
+    })
+}
@@ -0,0 +1,342 @@
+use crate::{
+    context::InitialResponder,
+    framework::{DefaultError, Framework},
+    twilight_exports::{
+        Interaction, InteractionResponse, InteractionResponseData, InteractionResponseType,
+        InteractionType,
+    },
+};
+use bytes::Bytes;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use http::{header::CONTENT_TYPE, Method, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper::{body::Incoming, server::conn::http1, service::service_fn};
+use hyper_util::rt::TokioIo;
+use std::{
+    convert::{Infallible, TryFrom},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+use tokio::{net::TcpListener, sync::oneshot};
+use tracing::{debug, warn};
+
+/// The header containing the signature of the request.
+pub const SIGNATURE_HEADER: &str = "X-Signature-Ed25519";
+/// The header containing the timestamp used to sign the request.
+pub const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+/// Discord waits three seconds for the initial response, leave some margin for the network.
+const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_millis(2500);
+/// How far the timestamp of a request can be from the current time before it's rejected.
+const DEFAULT_TIMESTAMP_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+/// Errors that can occur when handling a request received by the [endpoint](InteractionEndpoint).
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum EndpointError {
+    /// The provided public key is not a valid hex encoded Ed25519 key.
+    #[error("Invalid public key")]
+    InvalidPublicKey,
+    /// A required header is missing or is not valid.
+    #[error("Missing or invalid {0} header")]
+    MissingHeader(&'static str),
+    /// The signature of the request does not match its body.
+    #[error("Invalid request signature")]
+    InvalidSignature,
+    /// The timestamp of the request is outside the [tolerance](InteractionEndpoint::timestamp_tolerance)
+    /// of the endpoint, so the request may have been replayed.
+    #[error("Stale request timestamp")]
+    StaleTimestamp,
+    /// The body of the request is not a valid interaction.
+    #[error(transparent)]
+    Deserialize(#[from] serde_json::Error),
+}
+
+impl EndpointError {
+    /// The status code the endpoint responds with when this error happens.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidPublicKey => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::MissingHeader(_) | Self::InvalidSignature | Self::StaleTimestamp => {
+                StatusCode::UNAUTHORIZED
+            }
+            Self::Deserialize(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// An http request handler that receives interactions, verifies them and routes them through
+/// [Framework::process](Framework::process).
+///
+/// Every request is verified using the `X-Signature-Ed25519` and `X-Signature-Timestamp` headers
+/// against the public key of the application, rejecting requests whose timestamp is too far from
+/// the current time so captured requests can't be replayed. `Ping` interactions are answered
+/// directly and any other interaction is processed by the framework.
+///
+/// The initial response of the interaction is sent back as the body of the http request when it
+/// is created through the framework, this is, using [SlashContext::create_response],
+/// [SlashContext::defer] or [SlashContext::create_modal], and when autocompleting arguments.
+/// If the interaction is not responded within the [response timeout](Self::response_timeout), it
+/// is deferred and the command keeps running, and responses sent afterwards through the context
+/// replace the deferred one. If the
+/// processing finishes without creating an initial response through the framework, the request is
+/// answered with `202 Accepted`, expecting the response to be sent using the http api.
+///
+/// The handler is independent from any http server, so it can be used with any of them by calling
+/// [handle](Self::handle), or directly by using [serve](Self::serve).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use tokio::net::TcpListener;
+/// use twilight_http::Client;
+/// use twilight_model::id::Id;
+/// use vesper::{endpoint::InteractionEndpoint, prelude::*};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+///     let public_key = std::env::var("DISCORD_PUBLIC_KEY").unwrap();
+///
+///     let framework = Arc::new(Framework::<()>::builder(Client::new(token), Id::new(app_id), ()).build());
+///     let endpoint = InteractionEndpoint::new(framework, &public_key).unwrap();
+///
+///     let listener = TcpListener::bind("0.0.0.0:8080").await.unwrap();
+///     Arc::new(endpoint).serve(listener).await.unwrap();
+/// }
+/// ```
+///
+/// [SlashContext::create_response]: crate::context::SlashContext::create_response
+/// [SlashContext::defer]: crate::context::SlashContext::defer
+/// [SlashContext::create_modal]: crate::context::SlashContext::create_modal
+pub struct InteractionEndpoint<D, T = (), E = DefaultError> {
+    framework: Arc<Framework<D, T, E>>,
+    public_key: VerifyingKey,
+    response_timeout: Duration,
+    timestamp_tolerance: Duration,
+}
+
+impl<D, T, E> InteractionEndpoint<D, T, E>
+where
+    D: Send + Sync + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    /// Creates a new endpoint using the given framework and the hex encoded public key of the
+    /// application, which can be found in the developer portal.
+    pub fn new(framework: Arc<Framework<D, T, E>>, public_key: &str) -> Result<Self, EndpointError> {
+        let bytes = hex::decode(public_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or(EndpointError::InvalidPublicKey)?;
+
+        let public_key =
+            VerifyingKey::from_bytes(&bytes).map_err(|_| EndpointError::InvalidPublicKey)?;
+
+        Ok(Self {
+            framework,
+            public_key,
+            response_timeout: DEFAULT_RESPONSE_TIMEOUT,
+            timestamp_tolerance: DEFAULT_TIMESTAMP_TOLERANCE,
+        })
+    }
+
+    /// Sets the time to wait for the initial response before deferring the interaction,
+    /// by default two and a half seconds.
+    pub fn response_timeout(mut self, timeout: Duration) -> Self {
+        self.response_timeout = timeout;
+        self
+    }
+
+    /// Sets how far the timestamp of a request can be from the current time before the request is
+    /// rejected, by default five minutes.
+    pub fn timestamp_tolerance(mut self, tolerance: Duration) -> Self {
+        self.timestamp_tolerance = tolerance;
+        self
+    }
+
+    /// Gets the framework used to process interactions.
+    pub fn framework(&self) -> &Arc<Framework<D, T, E>> {
+        &self.framework
+    }
+
+    /// Verifies that the given body has been signed by discord, and that the timestamp is within the
+    /// [tolerance](Self::timestamp_tolerance) of the endpoint.
+    pub fn verify(&self, signature: &str, timestamp: &str, body: &[u8]) -> Result<(), EndpointError> {
+        let signature = hex::decode(signature)
+            .ok()
+            .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(EndpointError::MissingHeader(SIGNATURE_HEADER))?;
+
+        let mut message = Vec::with_capacity(timestamp.len() + body.len());
+        message.extend_from_slice(timestamp.as_bytes());
+        message.extend_from_slice(body);
+
+        self.public_key
+            .verify(&message, &signature)
+            .map_err(|_| EndpointError::InvalidSignature)?;
+
+        let sent_at = timestamp
+            .parse::<u64>()
+            .map_err(|_| EndpointError::MissingHeader(TIMESTAMP_HEADER))?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if now.abs_diff(sent_at) > self.timestamp_tolerance.as_secs() {
+            return Err(EndpointError::StaleTimestamp);
+        }
+
+        Ok(())
+    }
+
+    /// Handles the given http request, returning the response that must be sent back to discord.
+    pub async fn handle<B: AsRef<[u8]>>(&self, request: Request<B>) -> Response<Vec<u8>> {
+        if request.method() != Method::POST {
+            return empty_response(StatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        match self.try_handle(request).await {
+            Ok(response) => response,
+            Err(why) => {
+                debug!("Rejected interaction request: {}", why);
+                empty_response(why.status())
+            }
+        }
+    }
+
+    async fn try_handle<B: AsRef<[u8]>>(&self, request: Request<B>) -> Result<Response<Vec<u8>>, EndpointError> {
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .ok_or(EndpointError::MissingHeader(name))
+        };
+
+        let body = request.body().as_ref();
+        self.verify(header(SIGNATURE_HEADER)?, header(TIMESTAMP_HEADER)?, body)?;
+
+        let interaction = serde_json::from_slice::<Interaction>(body)?;
+
+        if interaction.kind == InteractionType::Ping {
+            return Ok(json_response(&InteractionResponse {
+                kind: InteractionResponseType::Pong,
+                data: None,
+            }));
+        }
+
+        let kind = interaction.kind;
+        let (sender, mut receiver) = oneshot::channel();
+        let responder = InitialResponder::new(sender);
+        let framework = Arc::clone(&self.framework);
+
+        tokio::spawn({
+            let responder = responder.clone();
+            async move {
+                framework.process_with_responder(interaction, responder).await;
+            }
+        });
+
+        tokio::select! {
+            response = &mut receiver => Ok(initial_response(response)),
+            _ = tokio::time::sleep(self.response_timeout) => {
+                if !responder.defer_on_behalf() {
+                    // The response was delivered right as the timeout elapsed.
+                    return Ok(initial_response(receiver.await));
+                }
+
+                warn!("Interaction was not responded in time, deferring it");
+                Ok(json_response(&deferred_response(kind)))
+            }
+        }
+    }
+
+    /// Accepts connections from the given listener, handling all the requests received. This also
+    /// spawns the [waiter sweeper](Framework::waiter_sweeper) of the framework.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> std::io::Result<()> {
+        tokio::spawn(self.framework.waiter_sweeper());
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let endpoint = Arc::clone(&self);
+
+            tokio::spawn(async move {
+                let service = service_fn(move |request: Request<Incoming>| {
+                    let endpoint = Arc::clone(&endpoint);
+
+                    async move {
+                        let (parts, body) = request.into_parts();
+                        let response = match body.collect().await {
+                            Ok(body) => endpoint.handle(Request::from_parts(parts, body.to_bytes())).await,
+                            Err(_) => empty_response(StatusCode::BAD_REQUEST),
+                        };
+
+                        Ok::<_, Infallible>(response.map(|body| Full::new(Bytes::from(body))))
+                    }
+                });
+
+                if let Err(why) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!("Error serving connection: {}", why);
+                }
+            });
+        }
+    }
+}
+
+/// The response sent with the initial response received from the framework.
+fn initial_response(
+    response: Result<InteractionResponse, oneshot::error::RecvError>,
+) -> Response<Vec<u8>> {
+    match response {
+        Ok(response) => json_response(&response),
+        // The processing finished without creating the initial response through the framework.
+        Err(_) => empty_response(StatusCode::ACCEPTED),
+    }
+}
+
+/// The response sent when the interaction was not responded in time.
+fn deferred_response(kind: InteractionType) -> InteractionResponse {
+    match kind {
+        InteractionType::ApplicationCommandAutocomplete => InteractionResponse {
+            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            data: Some(InteractionResponseData {
+                choices: Some(Vec::new()),
+                ..Default::default()
+            }),
+        },
+        InteractionType::MessageComponent | InteractionType::ModalSubmit => InteractionResponse {
+            kind: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        },
+        _ => InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: None,
+        },
+    }
+}
+
+fn json_response(response: &InteractionResponse) -> Response<Vec<u8>> {
+    let body = serde_json::to_vec(response).expect("Interaction responses are always serializable");
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .unwrap()
+}
+
+fn empty_response(status: StatusCode) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .body(Vec::new())
+        .unwrap()
+}
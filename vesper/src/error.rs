@@ -1,3 +1,5 @@
+use crate::{parse::ParseError, response::ResponseError};
+use std::time::Duration;
 use thiserror::Error;
 use twilight_validate::command::CommandValidationError;
 use twilight_http::{Error as HttpError, response::DeserializeBodyError};
@@ -10,3 +12,53 @@ pub enum CreateCommandError {
     Http(#[from] HttpError),
     Deserialize(#[from] DeserializeBodyError)
 }
+
+/// An error that occurred while executing a command or a component handler, given to the error
+/// handlers and to the `after` hook.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum FrameworkError<E> {
+    /// The arguments of the command failed to parse.
+    #[error("Failed to parse the arguments: {0}")]
+    Parse(ParseError),
+    /// A check returned an error.
+    #[error("A check returned an error: {0}")]
+    Check(E),
+    /// The framework failed to respond the interaction.
+    #[error("Failed to respond the interaction: {0}")]
+    Http(ResponseError),
+    /// The command did not finish before its timeout elapsed.
+    #[error("The command did not finish in {0:?}")]
+    TimedOut(Duration),
+    /// The command panicked, containing the panic message.
+    #[error("The command panicked: {0}")]
+    Panicked(String),
+    /// The command returned an error.
+    #[error("{0}")]
+    User(E),
+}
+
+impl<E> FrameworkError<E> {
+    /// Returns the error returned by the command or by a check, if this is one of them.
+    pub fn user_error(&self) -> Option<&E> {
+        match self {
+            Self::Check(error) | Self::User(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Converts this error into the error returned by the command or by a check, if this is one
+    /// of them.
+    pub fn into_user_error(self) -> Option<E> {
+        match self {
+            Self::Check(error) | Self::User(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<E> From<ParseError> for FrameworkError<E> {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
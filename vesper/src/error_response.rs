@@ -0,0 +1,106 @@
+use crate::{
+    error::FrameworkError,
+    parse::ParseError,
+    response::Reply,
+    twilight_exports::Interaction,
+};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// A function used to create the message shown to the user when a command fails and no error
+/// handler takes the error. Returning `None` sends no message for that kind of error.
+pub type ErrorResponder = fn(&Interaction, &ErrorKind, ErrorId) -> Option<Reply>;
+
+/// The kind of an error not taken by any error handler.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// An argument failed to parse.
+    Parsing {
+        /// The name of the argument that failed to parse.
+        argument_name: String,
+        /// The type of the argument.
+        argument_type: String,
+        /// The error message as a string.
+        error: String,
+    },
+    /// The received arguments don't match the ones of the command.
+    StructureMismatch(String),
+    /// A check returned an error.
+    Check,
+    /// The command returned an error.
+    Command,
+    /// The command did not finish before its timeout elapsed.
+    TimedOut,
+    /// The command panicked.
+    Panicked,
+}
+
+impl ErrorKind {
+    /// Gets the kind of the given error.
+    pub(crate) fn of<E>(error: &FrameworkError<E>) -> Self {
+        match error {
+            FrameworkError::Parse(error) => Self::from_parse_error(error).unwrap_or(Self::Command),
+            FrameworkError::Check(_) => Self::Check,
+            FrameworkError::TimedOut(_) => Self::TimedOut,
+            FrameworkError::Panicked(_) => Self::Panicked,
+            _ => Self::Command,
+        }
+    }
+
+    /// Gets the kind of the given parse error, if it is one of the user-facing ones.
+    fn from_parse_error(error: &ParseError) -> Option<Self> {
+        match error {
+            ParseError::Parsing {
+                argument_name,
+                argument_type,
+                error,
+                ..
+            } => Some(Self::Parsing {
+                argument_name: argument_name.clone(),
+                argument_type: argument_type.clone(),
+                error: error.clone(),
+            }),
+            ParseError::StructureMismatch(why) => Some(Self::StructureMismatch(why.clone())),
+            ParseError::Other(_) => None,
+        }
+    }
+}
+
+/// An id identifying a failed execution, included in the logs and in the message shown to the
+/// user, so the logs of an error reported by a user can be found.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorId(u64);
+
+impl ErrorId {
+    /// Creates the id of the failed execution of the given interaction.
+    pub(crate) fn new(interaction: &Interaction) -> Self {
+        Self(interaction.id.get())
+    }
+}
+
+impl Display for ErrorId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+/// The [responder](ErrorResponder) used by default, sending an ephemeral message describing
+/// the error along with its id.
+pub fn default_error_response(_: &Interaction, kind: &ErrorKind, id: ErrorId) -> Option<Reply> {
+    let message = match kind {
+        ErrorKind::Parsing {
+            argument_name,
+            error,
+            ..
+        } => format!("The value of `{}` is not valid: {}", argument_name, error),
+        ErrorKind::StructureMismatch(_) => {
+            String::from("The command received unexpected arguments, it may have been updated recently.")
+        }
+        ErrorKind::Check => String::from("An error occurred while checking if you can use this command."),
+        ErrorKind::Command => String::from("An error occurred while running this command."),
+        ErrorKind::TimedOut => String::from("The command took too long to finish."),
+        ErrorKind::Panicked => String::from("An unexpected error occurred while running this command."),
+    };
+
+    Some(Reply::new().content(format!("{}\nError id: `{}`", message, id)).ephemeral())
+}
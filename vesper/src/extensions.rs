@@ -0,0 +1,101 @@
+//! A map storing a value of each type, used to pass data to commands.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::Arc,
+};
+
+/// A map storing up to one value of each type, used by [layers](crate::middleware::Layer) and
+/// hooks to pass data to the command being executed, like the id of a request or the settings of
+/// the guild.
+///
+/// ```rust
+/// use vesper::extensions::Extensions;
+///
+/// struct RequestId(u64);
+///
+/// let mut extensions = Extensions::new();
+/// extensions.insert(RequestId(7));
+///
+/// assert_eq!(extensions.get::<RequestId>().map(|id| id.0), Some(7));
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value, replacing the previous value of the same type.
+    pub fn insert<V: Any + Send + Sync>(&mut self, value: V) {
+        self.map.insert(TypeId::of::<V>(), Arc::new(value));
+    }
+
+    /// Gets the value of the given type.
+    pub fn get<V: Any + Send + Sync>(&self) -> Option<&V> {
+        self.map.get(&TypeId::of::<V>())?.downcast_ref()
+    }
+
+    /// Gets a mutable reference to the value of the given type. Returns `None` if there is no
+    /// value of that type or if it is shared with a clone of the map.
+    pub fn get_mut<V: Any + Send + Sync>(&mut self) -> Option<&mut V> {
+        Arc::get_mut(self.map.get_mut(&TypeId::of::<V>())?)?.downcast_mut()
+    }
+
+    /// Removes the value of the given type, returning whether there was one.
+    pub fn remove<V: Any + Send + Sync>(&mut self) -> bool {
+        self.map.remove(&TypeId::of::<V>()).is_some()
+    }
+
+    /// Returns whether there is a value of the given type.
+    pub fn contains<V: Any + Send + Sync>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<V>())
+    }
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Extensions").field("len", &self.map.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_a_value_per_type() {
+        let mut extensions = Extensions::new();
+        extensions.insert(1u8);
+        extensions.insert(String::from("a"));
+        extensions.insert(2u8);
+
+        assert_eq!(extensions.get::<u8>(), Some(&2));
+        assert_eq!(extensions.get::<String>().map(String::as_str), Some("a"));
+        assert!(!extensions.contains::<u16>());
+
+        *extensions.get_mut::<u8>().unwrap() += 1;
+        assert_eq!(extensions.get::<u8>(), Some(&3));
+
+        assert!(extensions.remove::<u8>());
+        assert!(!extensions.contains::<u8>());
+    }
+
+    #[test]
+    fn shared_values_are_not_mutable() {
+        let mut extensions = Extensions::new();
+        extensions.insert(1u8);
+
+        let clone = extensions.clone();
+        assert!(extensions.get_mut::<u8>().is_none());
+
+        drop(clone);
+        assert!(extensions.get_mut::<u8>().is_some());
+    }
+}
@@ -2,6 +2,7 @@ mod sealed {
     pub trait Sealed {}
     impl<T, E> Sealed for Result<T, E> {}
     impl<T> Sealed for Option<T> {}
+    impl<E> Sealed for crate::error::FrameworkError<E> {}
 
     pub trait SealedDataOption: Sized {}
     impl SealedDataOption for String {}
@@ -24,6 +25,15 @@ pub trait Optional: sealed::Sealed {
     type Inner;
 }
 
+/// Used in the [`error handler`] and [`after hook`] to determine the error type returned by the
+/// command from the [framework error](crate::error::FrameworkError) they receive.
+///
+/// [`error handler`]: crate::hook::ErrorHandlerHook
+/// [`after hook`]: crate::hook::AfterHook
+pub trait UserError: sealed::Sealed {
+    type Inner;
+}
+
 /// Defines what data types can be used when creating a modal.
 pub trait ModalDataOption: sealed::SealedDataOption {
     fn required() -> bool;
@@ -39,6 +49,10 @@ impl<T> Optional for Option<T> {
     type Inner = T;
 }
 
+impl<E> UserError for crate::error::FrameworkError<E> {
+    type Inner = E;
+}
+
 impl ModalDataOption for Option<String> {
     fn required() -> bool {
         false
@@ -1,22 +1,37 @@
 use crate::{
     argument::CommandArgument,
     builder::{FrameworkBuilder, WrappedClient},
-    command::{Command, CommandMap, ExecutionState, OutputLocation},
-    context::{AutocompleteContext, Focused, SlashContext},
-    group::GroupParentMap,
-    hook::{AfterHook, BeforeHook},
+    check::{Denial, DenialResponder},
+    command::{Command, CommandInfo, CommandMap, ExecutionState, OutputLocation},
+    component::{ComponentHandler, ComponentKind},
+    concurrency::{Concurrency, ConcurrencyLimiter, ConcurrencyMode, ConcurrencyPermit},
+    context::{AutocompleteContext, Focused, InitialResponder, SlashContext},
+    cooldown::{Bucket, CooldownKey, CooldownStorage},
+    custom_id::CustomIdCodec,
+    defer::Defer,
+    dispatch::catch_panics,
+    error_response::{ErrorId, ErrorKind, ErrorResponder},
+    group::{GroupParent, GroupParentMap},
+    hook::{AfterHook, BeforeHook, CooldownHook, ErrorHandlerHook},
+    middleware::{Layers, Next},
     twilight_exports::{
         ApplicationMarker, Client,
         Command as TwilightCommand, CommandDataOption, CommandOptionType,
-        CommandOptionValue, GuildMarker, Id, Interaction, InteractionData, InteractionType, InteractionClient, InteractionResponse,
+        CommandOptionValue, GuildMarker, Id, Interaction, InteractionData, Permissions, InteractionType, InteractionClient, InteractionResponse,
         InteractionResponseType,
     },
-    wait::WaiterWaker, prelude::CreateCommandError
+    wait::WaiterWaker, prelude::CreateCommandError,
+    response::{Reply, ResponseError, ResponseState},
+    error::FrameworkError
 };
-use tracing::debug;
+use tracing::{debug, warn};
 use parking_lot::Mutex;
+use std::{
+    future::Future,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 use crate::command::ExecutionResult;
-use crate::parse::ParseError;
 #[cfg(feature = "bulk")]
 use crate::if_some;
 
@@ -31,28 +46,60 @@ macro_rules! extract {
 
 /// The result of a `.process` call, containing the state of the interaction handling.
 #[non_exhaustive]
+#[derive(Debug)]
 pub enum ProcessResult<T, E> {
     /// The specified command was not found, either to execute its handler or to try to autocomplete
     /// an argument.
     CommandNotFound,
-    /// The interaction was a modal submit interaction.
+    /// The interaction was a modal submit interaction not routed to any handler.
     ModalSubmit,
-    /// The interaction was a message component interaction.
+    /// The interaction was a message component interaction not routed to any handler.
     MessageComponent,
+    /// The interaction was routed to a [component handler](ComponentHandler).
+    ComponentHandled(ExecutionResult<T, E>),
     /// The specified command argument was autocompleted successufully.
     Autocompleted,
+    /// The framework failed to respond the interaction. Only the failures of autocomplete
+    /// interactions are returned here, since the ones of commands are given to the error handlers.
+    Failed(FrameworkError<E>),
     /// The specified command was executed.
     CommandExecuted(ExecutionResult<T, E>),
     /// The interaction type is not supported. This should unly happen with `Ping` interactions.
     UnknownInteraction
 }
 
+/// The minimum interval between two sweeps of the waiters.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Removes the waiters which have been dropped or cancelled from the given list.
+fn sweep(waiters: &Mutex<Vec<WaiterWaker>>) {
+    waiters.lock().retain(|waker| !waker.is_closed());
+}
+
+/// Sweeps the given waiters every [SWEEP_INTERVAL] until they are dropped along the framework.
+async fn sweep_periodically(waiters: Weak<Mutex<Vec<WaiterWaker>>>) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+    // The first tick completes immediately.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        match waiters.upgrade() {
+            Some(waiters) => sweep(&waiters),
+            None => break
+        }
+    }
+}
+
 /// The default error used by the framework.
 pub type DefaultError = Box<dyn std::error::Error + Send + Sync>;
 
 /// A generic return type for commands provided by the framework.
 pub type DefaultCommandResult = Result<(), DefaultError>;
 
+/// The result given to the `after` hook for commands returning a [DefaultCommandResult].
+pub type DefaultFrameworkResult = Result<(), FrameworkError<DefaultError>>;
+
 /// The framework used to dispatch slash commands.
 pub struct Framework<D, T = (), E = DefaultError> {
     /// The http client used by the framework.
@@ -66,16 +113,40 @@ pub struct Framework<D, T = (), E = DefaultError> {
     /// A map of command groups including all children.
     pub groups: GroupParentMap<D, T, E>,
     /// A hook executed before the command.
-    pub before: Option<BeforeHook<D>>,
+    pub before: Option<BeforeHook<D, T, E>>,
     /// A hook executed after command's execution.
     pub after: Option<AfterHook<D, T, E>>,
-    pub waiters: Mutex<Vec<WaiterWaker>>
+    /// The handlers of component and modal interactions.
+    pub components: Vec<ComponentHandler<D, T, E>>,
+    /// The codec used to encode and decode typed custom ids.
+    pub custom_ids: CustomIdCodec,
+    /// The storage used to keep track of command cooldowns.
+    pub cooldown_storage: Box<dyn CooldownStorage>,
+    /// A hook used to tell the user a command is on cooldown.
+    pub cooldown_responder: Option<CooldownHook<D>>,
+    /// The error handler used when a command or component handler does not have its own.
+    pub error_handler: Option<ErrorHandlerHook<D, T, E>>,
+    /// The function creating the message shown to the user when no error handler takes an error,
+    /// if any.
+    pub error_responder: Option<ErrorResponder>,
+    /// The function creating the message shown to the user when a check denies the execution.
+    pub denial_responder: DenialResponder,
+    /// When the interactions of commands without their own policy are automatically deferred.
+    pub auto_defer: Option<Defer>,
+    /// The response sent when a command subject to a defer policy finishes without responding.
+    pub fallback_response: Reply,
+    /// Whether the permissions of the member are checked every time a command is used.
+    pub strict_permissions: bool,
+    /// The maximum time commands without their own timeout can run.
+    pub command_timeout: Option<Duration>,
+    /// The layers wrapping the execution of every command.
+    pub layers: Layers<D, T, E>,
+    pub waiters: Arc<Mutex<Vec<WaiterWaker>>>,
+    /// The executions running for the commands with a concurrency limit.
+    concurrency: ConcurrencyLimiter
 }
 
-impl<D, T, E> Framework<D, T, E>
-where
-    E: From<ParseError>
-{
+impl<D, T, E> Framework<D, T, E> {
     pub(crate) fn from_builder(builder: FrameworkBuilder<D, T, E>) -> Self {
         Self {
             http_client: builder.http_client,
@@ -85,7 +156,20 @@ where
             groups: builder.groups,
             before: builder.before,
             after: builder.after,
-            waiters: Mutex::new(Vec::new())
+            components: builder.components,
+            custom_ids: builder.custom_ids,
+            cooldown_storage: builder.cooldown_storage,
+            cooldown_responder: builder.cooldown_responder,
+            error_handler: builder.error_handler,
+            error_responder: builder.error_responder,
+            denial_responder: builder.denial_responder,
+            auto_defer: builder.auto_defer,
+            fallback_response: builder.fallback_response,
+            strict_permissions: builder.strict_permissions,
+            command_timeout: builder.command_timeout,
+            layers: builder.layers,
+            waiters: Arc::new(Mutex::new(Vec::new())),
+            concurrency: ConcurrencyLimiter::new()
         }
     }
 
@@ -106,41 +190,119 @@ where
 
     /// Gets the [interaction client](InteractionClient) using this framework's
     /// [http client](Client) and [application id](ApplicationMarker)
-    pub fn interaction_client(&self) -> InteractionClient {
+    pub fn interaction_client(&self) -> InteractionClient<'_> {
         self.http_client().interaction(self.application_id)
     }
 
     /// Processes the given interaction, dispatching commands or waking waiters if necessary.
-    pub async fn process(&self, mut interaction: Interaction) -> ProcessResult<T, E> {
+    pub async fn process(&self, interaction: Interaction) -> ProcessResult<T, E> {
+        self.process_with_responder(interaction, InitialResponder::default()).await
+    }
+
+    /// Processes the given interaction, delivering its initial response using the given
+    /// [responder](InitialResponder).
+    pub(crate) async fn process_with_responder(
+        &self,
+        interaction: Interaction,
+        responder: InitialResponder
+    ) -> ProcessResult<T, E> {
         match interaction.kind {
             InteractionType::ApplicationCommand => {
-                let Some(command) = self.get_command(&mut interaction) else {
-                    self.wake_waiters(interaction);
+                let Some(command) = self.get_command(&interaction) else {
+                    let _ = self.wake_waiters(interaction);
                     return ProcessResult::CommandNotFound;
                 };
-                self.execute(command, interaction).await.into()
+                self.execute(command, interaction, responder).await.into()
             },
-            InteractionType::ApplicationCommandAutocomplete => self.try_autocomplete(interaction).await,
-            InteractionType::MessageComponent  => {
-                self.wake_waiters(interaction);
-                ProcessResult::MessageComponent
+            InteractionType::ApplicationCommandAutocomplete => {
+                self.try_autocomplete(interaction, responder).await
             },
-            InteractionType::ModalSubmit => {
-                self.wake_waiters(interaction);
-                ProcessResult::ModalSubmit
+            InteractionType::MessageComponent | InteractionType::ModalSubmit => {
+                let kind = interaction.kind;
+                let Some(interaction) = self.wake_waiters(interaction) else {
+                    return if kind == InteractionType::ModalSubmit {
+                        ProcessResult::ModalSubmit
+                    } else {
+                        ProcessResult::MessageComponent
+                    };
+                };
+
+                match self.get_component_handler(&interaction) {
+                    Some((handler, remainder)) => {
+                        self.execute_component(handler, remainder, interaction, responder).await
+                    },
+                    None if kind == InteractionType::ModalSubmit => ProcessResult::ModalSubmit,
+                    None => ProcessResult::MessageComponent
+                }
             },
             _ => ProcessResult::UnknownInteraction
         }
     }
 
-    fn wake_waiters(&self, interaction: Interaction) {
+    /// Removes all the waiters which have been dropped or cancelled without removing
+    /// themselves from the framework.
+    pub fn sweep_waiters(&self) {
+        sweep(&self.waiters);
+    }
+
+    /// Returns a future [sweeping the waiters](Self::sweep_waiters) every minute, which must be
+    /// spawned into the runtime. The future completes once the framework is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use vesper::prelude::*;
+    /// use twilight_http::Client;
+    /// use twilight_model::id::Id;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = std::env::var("DISCORD_TOKEN").unwrap();
+    ///     let app_id = std::env::var("DISCORD_APP_ID").unwrap().parse::<u64>().unwrap();
+    ///
+    ///     let framework = Framework::<()>::builder(Client::new(token), Id::new(app_id), ()).build();
+    ///     tokio::spawn(framework.waiter_sweeper());
+    /// }
+    /// ```
+    pub fn waiter_sweeper(&self) -> impl Future<Output = ()> + Send + 'static {
+        sweep_periodically(Arc::downgrade(&self.waiters))
+    }
+
+    /// Delivers the interaction to the first waiter it satisfies, returning it back if no waiter
+    /// claimed it.
+    fn wake_waiters(&self, interaction: Interaction) -> Option<Interaction> {
         let mut lock = self.waiters.lock();
         if let Some(position) = lock.iter().position(|waker| waker.check(&interaction)) {
-            lock.remove(position).wake(interaction);
+            if lock[position].is_persistent() {
+                lock[position].deliver(interaction);
+            } else {
+                lock.remove(position).wake(interaction);
+            }
+
+            return None;
         }
+
+        Some(interaction)
+    }
+
+    /// Gets the most specific [component handler](ComponentHandler) matching the custom id of
+    /// the given interaction, along with the remainder of the custom id.
+    fn get_component_handler(&self, interaction: &Interaction) -> Option<(&ComponentHandler<D, T, E>, String)> {
+        let (kind, custom_id) = ComponentKind::of(interaction)?;
+
+        self.components
+            .iter()
+            .filter(|handler| handler.kind == kind)
+            .filter_map(|handler| Some((handler, handler.route.matches(custom_id)?)))
+            .max_by_key(|(handler, _)| handler.route.specificity())
+            .map(|(handler, remainder)| (handler, remainder.to_string()))
     }
 
-    async fn try_autocomplete(&self, mut interaction: Interaction) -> ProcessResult<T, E> {
+    async fn try_autocomplete(
+        &self,
+        mut interaction: Interaction,
+        responder: InitialResponder
+    ) -> ProcessResult<T, E> {
         if let Some((name, argument, value)) = self.get_autocomplete_argument(&interaction) {
             if let Some(fun) = &argument.autocomplete {
                 let context = AutocompleteContext::new(
@@ -151,18 +313,22 @@ where
                 );
                 debug!("Command [{}] executing argument {} autocomplete function", name, argument.name);
                 let data = (fun.0)(context).await;
+                let response = InteractionResponse {
+                    kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+                    data,
+                };
 
-                let _ = self
-                    .interaction_client()
-                    .create_response(
-                        interaction.id,
-                        &interaction.token,
-                        &InteractionResponse {
-                            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
-                            data,
-                        },
-                    )
-                    .await;
+                if !responder.deliver(&response) {
+                    let result = self
+                        .interaction_client()
+                        .create_response(interaction.id, &interaction.token, &response)
+                        .await;
+
+                    if let Err(why) = result {
+                        warn!("Failed to autocomplete argument {} of command [{}]: {}", argument.name, name, why);
+                        return ProcessResult::Failed(FrameworkError::Http(why.into()));
+                    }
+                }
 
                 return ProcessResult::Autocompleted;
             }
@@ -177,10 +343,10 @@ where
     ) -> Option<(&str, &CommandArgument<D, T, E>, Focused)> {
         let data = extract!(interaction.data.as_ref().unwrap() => ApplicationCommand);
         if !data.options.is_empty() {
-            let outer = data.options.get(0)?;
+            let outer = data.options.first()?;
             let focused = match &outer.value {
                 CommandOptionValue::SubCommandGroup(sc_group) => {
-                    let next = sc_group.get(0)?;
+                    let next = sc_group.first()?;
                     if let CommandOptionValue::SubCommand(options) = &next.value {
                         self.get_focus(options)
                     } else {
@@ -252,68 +418,460 @@ where
 
     /// Gets the next [option](CommandDataOption)
     /// only if it corresponds to a subcommand or a subcommand group.
-    fn get_next<'a>(&self, interaction: &'a Vec<CommandDataOption>) -> Option<&'a CommandDataOption> {
+    fn get_next<'a>(&self, interaction: &'a [CommandDataOption]) -> Option<&'a CommandDataOption> {
         if !interaction.is_empty()
             && (interaction[0].value.kind() == CommandOptionType::SubCommand
                 || interaction[0].value.kind() == CommandOptionType::SubCommandGroup)
         {
-            interaction.get(0)
+            interaction.first()
         } else {
             None
         }
     }
 
     /// Executes the given [command](crate::command::Command) and the hooks.
-    async fn execute(&self, cmd: &Command<D, T, E>, interaction: Interaction) -> ExecutionResult<T, E> {
+    async fn execute(
+        &self,
+        cmd: &Command<D, T, E>,
+        interaction: Interaction,
+        responder: InitialResponder
+    ) -> ExecutionResult<T, E> {
+        catch_panics("Command", cmd.name, self.execute_command(cmd, interaction, responder)).await
+    }
+
+    /// Executes the given command along with its hooks and layers.
+    async fn execute_command(
+        &self,
+        cmd: &Command<D, T, E>,
+        interaction: Interaction,
+        responder: InitialResponder
+    ) -> ExecutionResult<T, E> {
         let mut context = SlashContext::new(
             &self.http_client,
             self.application_id,
             &self.data,
             &self.waiters,
+            &self.custom_ids,
             interaction,
+            responder,
         );
 
+        let info = CommandInfo::for_command(cmd, &context);
+
         let execute = if let Some(before) = &self.before {
-            (before.0)(&mut context, cmd.name).await
+            (before.0)(&mut context, &info).await
         } else {
             true
         };
 
-        if execute {
-            let mut result = cmd.execute(&mut context).await;
-
-            match (&self.after, result.state) {
-                // The after hook should not execute if any check returned false or a check errored.
-                (Some(after), 
-                ExecutionState::CommandFinished 
-                | ExecutionState::CommandErrored) => {
-                    // Set the output as taken, if it was already taken, we'll restore it to the previous state.
-                    let output = std::mem::replace(&mut result.output, OutputLocation::TakenByAfterHook);
-
-                    let output = if let OutputLocation::Present(return_value) = output {
-                        // If the output is not taken beforehand by the error handler, leave it as taken
-                        // by the after hook one.
-                        Some(return_value)
-                    } else {
-                        // If it was taken, return it to it's previous state.
-                        result.output = output;
-                        None
-                    };
+        if !execute {
+            return ExecutionResult {
+                state: ExecutionState::BeforeHookFailed,
+                output: OutputLocation::NotExecuted,
+                error_id: None,
+                denial: None
+            };
+        }
 
-                    (after.0)(&mut context, cmd.name, output).await;
-                },
-                _ => ()
-            }
+        let group = self.get_group(&context.interaction);
+        let layers = self.layers
+            .iter()
+            .chain(group.into_iter().flat_map(|group| group.layers.iter()))
+            .chain(cmd.layers.iter())
+            .map(|layer| &**layer)
+            .collect::<Vec<_>>();
+
+        let mut result = match self.admit(cmd, &info, &mut context).await {
+            // The permit is held until the layers and the command finish, freeing its slot
+            // afterwards.
+            Ok(_permit) => Next::new(self, cmd, &info, &layers).run(&mut context).await,
+            Err(result) => result,
+        };
+        self.handle_error(&mut context, &info, &mut result).await;
+        self.respond_denial(&mut context, &info, result.denial.as_ref()).await;
+        self.run_after_hook(&mut context, &info, &mut result).await;
 
-            result
+        if cmd.defer.or(self.auto_defer).is_some() {
+            self.send_fallback(&mut context, &info, result.state).await;
+        }
+
+        result
+    }
+
+    /// Decides whether the given command can be executed, enforcing its permissions, concurrency
+    /// limit, checks and cooldown. Returns the permit of its concurrency limit, which must be held
+    /// while the command runs, or the result of the execution if it was rejected.
+    async fn admit(
+        &self,
+        cmd: &Command<D, T, E>,
+        info: &CommandInfo<'_, D, T, E>,
+        context: &mut SlashContext<'_, D>
+    ) -> Result<Option<ConcurrencyPermit>, ExecutionResult<T, E>> {
+        if let Some((state, denial)) = self.check_permissions(cmd, &context.interaction) {
+            debug!("Command [{}] is missing permissions: {:?}", cmd.name, state);
+            return Err(ExecutionResult {
+                state,
+                output: OutputLocation::NotExecuted,
+                error_id: None,
+                denial: Some(denial)
+            });
+        }
+
+        let permit = match &cmd.concurrency {
+            Some(concurrency) => {
+                let defer = cmd.defer.or(self.auto_defer);
+                match self.acquire_concurrency(concurrency, context, defer).await {
+                    Some(permit) => Some(permit),
+                    None => {
+                        debug!("Command [{}] reached its concurrency limit", cmd.name);
+                        return Err(ExecutionResult {
+                            state: ExecutionState::ConcurrencyLimited,
+                            output: OutputLocation::NotExecuted,
+                            error_id: None,
+                            denial: Some(Denial::ConcurrencyLimited)
+                        });
+                    }
+                }
+            },
+            None => None
+        };
+
+        // The cooldown is only consumed once the checks passed, so denied invocations don't use it.
+        cmd.pass_checks(context, info).await?;
+
+        if let Some(remaining) = self.check_cooldown(cmd, &context.interaction).await {
+            debug!("Command [{}] is on cooldown for {:?}", cmd.name, remaining);
+            return Err(ExecutionResult {
+                state: ExecutionState::OnCooldown(remaining),
+                output: OutputLocation::NotExecuted,
+                error_id: None,
+                denial: Some(Denial::Cooldown(remaining))
+            });
+        }
+
+        Ok(permit)
+    }
+
+    /// Runs the given command once it was admitted and all its
+    /// [layers](crate::middleware::Layer) have run. This runs again every time a layer runs the
+    /// rest of the stack.
+    pub(crate) async fn run_command(
+        &self,
+        cmd: &Command<D, T, E>,
+        info: &CommandInfo<'_, D, T, E>,
+        context: &mut SlashContext<'_, D>
+    ) -> ExecutionResult<T, E> {
+        let defer = cmd.defer.or(self.auto_defer);
+        let timeout = cmd.timeout.or(self.command_timeout);
+        cmd.run_checked(context, info, defer, timeout).await
+    }
+
+    /// Executes the given [component handler](ComponentHandler) and the hooks.
+    async fn execute_component(
+        &self,
+        handler: &ComponentHandler<D, T, E>,
+        remainder: String,
+        interaction: Interaction,
+        responder: InitialResponder
+    ) -> ProcessResult<T, E> {
+        let execution = self.execute_handler(handler, remainder, interaction, responder);
+        ProcessResult::ComponentHandled(catch_panics("Component handler", handler.name, execution).await)
+    }
+
+    /// Executes the given component handler along with the hooks.
+    async fn execute_handler(
+        &self,
+        handler: &ComponentHandler<D, T, E>,
+        remainder: String,
+        interaction: Interaction,
+        responder: InitialResponder
+    ) -> ExecutionResult<T, E> {
+        let mut context = SlashContext::new(
+            &self.http_client,
+            self.application_id,
+            &self.data,
+            &self.waiters,
+            &self.custom_ids,
+            interaction,
+            responder,
+        );
+
+        let info = CommandInfo::for_component(handler);
+
+        let execute = if let Some(before) = &self.before {
+            (before.0)(&mut context, &info).await
         } else {
-            ExecutionResult {
+            true
+        };
+
+        if !execute {
+            return ExecutionResult {
                 state: ExecutionState::BeforeHookFailed,
-                output: OutputLocation::NotExecuted
+                output: OutputLocation::NotExecuted,
+                error_id: None,
+                denial: None
+            };
+        }
+
+        let mut result = handler
+            .execute_with(&mut context, &info, &remainder, self.command_timeout)
+            .await;
+        self.handle_error(&mut context, &info, &mut result).await;
+        self.respond_denial(&mut context, &info, result.denial.as_ref()).await;
+        self.run_after_hook(&mut context, &info, &mut result).await;
+
+        result
+    }
+
+    /// Assigns an id to the error of the execution, if any, and gives the error to the error
+    /// handler of the framework. If no error handler takes the error, the user is told about it
+    /// using the error responder, if set.
+    async fn handle_error(
+        &self,
+        context: &mut SlashContext<'_, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        result: &mut ExecutionResult<T, E>
+    ) {
+        let kind = match (&result.output, result.state) {
+            (OutputLocation::Present(Err(why)), _) => ErrorKind::of(why),
+            (_, ExecutionState::CheckErrored) => ErrorKind::Check,
+            (_, ExecutionState::CommandErrored) => ErrorKind::Command,
+            (_, ExecutionState::TimedOut(_)) => ErrorKind::TimedOut,
+            (_, ExecutionState::Panicked) => ErrorKind::Panicked,
+            _ => return
+        };
+
+        let id = ErrorId::new(&context.interaction);
+        result.error_id = Some(id);
+        warn!("Execution of [{}] failed with error id {} ({:?})", info.path(), id, kind);
+
+        if !matches!(result.output, OutputLocation::Present(Err(_))) {
+            // The error was already taken by the error handler of the command.
+            return;
+        }
+
+        if let Some(handler) = &self.error_handler {
+            let output = std::mem::replace(&mut result.output, OutputLocation::TakenByErrorHandler);
+            if let OutputLocation::Present(Err(why)) = output {
+                (handler.0)(context, info, why).await;
+            }
+
+            return;
+        }
+
+        let Some(responder) = self.error_responder else {
+            return;
+        };
+
+        if let Some(reply) = responder(&context.interaction, &kind, id) {
+            if let Err(why) = context.reply(reply).await {
+                debug!("Failed to send error response: {}", why);
+            }
+        }
+    }
+
+    /// Tells the user why the execution was denied using the denial responder, or the cooldown
+    /// responder for cooldowns, unless the interaction was already responded.
+    async fn respond_denial(
+        &self,
+        context: &mut SlashContext<'_, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        denial: Option<&Denial>
+    ) {
+        let Some(denial) = denial else {
+            return;
+        };
+
+        if context.response_state().is_acknowledged() {
+            return;
+        }
+
+        // Cooldowns are told using the cooldown responder instead, if there is one.
+        if let (Denial::Cooldown(remaining), Some(responder)) = (denial, &self.cooldown_responder) {
+            (responder.0)(context, *remaining).await;
+            return;
+        }
+
+        if let Some(reply) = (self.denial_responder)(&context.interaction, denial) {
+            if let Err(why) = context.reply(reply).await {
+                self.report_response_error(context, info, why).await;
+            }
+        }
+    }
+
+    /// Gives an error that occurred while the framework responded the interaction to the error
+    /// handler of the framework, if any.
+    async fn report_response_error(
+        &self,
+        context: &mut SlashContext<'_, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        error: ResponseError
+    ) {
+        debug!("Failed to respond the interaction of [{}]: {}", info.path(), error);
+
+        if let Some(handler) = &self.error_handler {
+            (handler.0)(context, info, FrameworkError::Http(error)).await;
+        }
+    }
+
+    /// Executes the after hook if the command executed, giving it the output if it was not taken
+    /// by the error handler.
+    async fn run_after_hook(
+        &self,
+        context: &mut SlashContext<'_, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        result: &mut ExecutionResult<T, E>
+    ) {
+        match (&self.after, result.state) {
+            // If the execution was denied, by a check or by the cooldown, concurrency limit or
+            // permissions of the command, the after hook receives the reason.
+            (Some(after), _) if result.denial.is_some() => {
+                (after.0)(context, info, None, result.denial.clone()).await;
+            },
+            // The after hook should not execute if a check errored.
+            (Some(after),
+            ExecutionState::CommandFinished
+            | ExecutionState::CommandErrored
+            | ExecutionState::TimedOut(_)
+            | ExecutionState::Panicked) => {
+                // Set the output as taken, if it was already taken, we'll restore it to the previous state.
+                let output = std::mem::replace(&mut result.output, OutputLocation::TakenByAfterHook);
+
+                let output = if let OutputLocation::Present(return_value) = output {
+                    // If the output is not taken beforehand by the error handler, leave it as taken
+                    // by the after hook one.
+                    Some(return_value)
+                } else {
+                    // If it was taken, return it to it's previous state.
+                    result.output = output;
+                    None
+                };
+
+                (after.0)(context, info, output, None).await;
+            },
+            _ => ()
+        }
+    }
+
+    /// Sends the fallback response if the command executed without responding the interaction.
+    async fn send_fallback(
+        &self,
+        context: &mut SlashContext<'_, D>,
+        info: &CommandInfo<'_, D, T, E>,
+        state: ExecutionState
+    ) {
+        let executed = matches!(
+            state,
+            ExecutionState::CommandFinished
+                | ExecutionState::CommandErrored
+                | ExecutionState::TimedOut(_)
+                | ExecutionState::Panicked
+        );
+
+        if !executed {
+            return;
+        }
+
+        // A deferred response is kept as is, since it may have been edited using the interaction
+        // client directly, which doesn't update the response state.
+        if context.response_state() != ResponseState::Unanswered {
+            return;
+        }
+
+        debug!("Command [{}] finished without responding, sending fallback response", info.path());
+        if let Err(why) = context.reply(self.fallback_response.clone()).await {
+            self.report_response_error(context, info, why).await;
+        }
+    }
+
+    /// Checks the permissions of the bot, and of the member if strict permissions are enabled,
+    /// returning the state of the execution and the denial if any permission is missing.
+    fn check_permissions(&self, cmd: &Command<D, T, E>, interaction: &Interaction) -> Option<(ExecutionState, Denial)> {
+        let group = self.get_group(interaction);
+        let required_bot = merge_permissions(
+            cmd.required_bot_permissions,
+            group.and_then(|group| group.required_bot_permissions)
+        );
+
+        if let (Some(required), Some(available)) = (required_bot, interaction.app_permissions) {
+            let missing = missing_permissions(required, available);
+            if !missing.is_empty() {
+                return Some((ExecutionState::MissingBotPermissions(missing), Denial::MissingBotPermissions(missing)));
+            }
+        }
+
+        if !self.strict_permissions {
+            return None;
+        }
+
+        let required = merge_permissions(
+            cmd.required_permissions,
+            group.and_then(|group| group.required_permissions)
+        );
+        let available = interaction.member.as_ref().and_then(|member| member.permissions);
+
+        // Permissions don't apply outside of guilds, where there is no member.
+        if let (Some(required), Some(available)) = (required, available) {
+            let missing = missing_permissions(required, available);
+            if !missing.is_empty() {
+                return Some((ExecutionState::MissingMemberPermissions(missing), Denial::MissingPermissions(missing)));
+            }
+        }
+
+        None
+    }
+
+    /// Gets the [group parent](GroupParent) of the command of the given interaction, returning
+    /// `None` if the command is not inside a group.
+    fn get_group(&self, interaction: &Interaction) -> Option<&GroupParent<D, T, E>> {
+        let data = extract!(interaction.data.as_ref()? => ApplicationCommand);
+        self.get_next(&data.options)?;
+        self.groups.get(&*data.name)
+    }
+
+    /// Starts an execution of a command with the given concurrency limit, returning `None` if
+    /// the limit was reached and the execution is rejected. Queued executions defer the
+    /// interaction before waiting if the command is subject to a defer policy.
+    async fn acquire_concurrency(
+        &self,
+        concurrency: &Concurrency,
+        context: &SlashContext<'_, D>,
+        defer: Option<Defer>
+    ) -> Option<ConcurrencyPermit> {
+        let command = command_path(&context.interaction);
+        let bucket = Bucket::new(concurrency.bucket, &context.interaction);
+
+        if let Some(permit) = self.concurrency.try_acquire(&command, bucket, concurrency) {
+            return Some(permit);
+        }
+
+        match concurrency.mode {
+            ConcurrencyMode::Reject => None,
+            ConcurrencyMode::Queue => {
+                // Waiting can take longer than the time discord gives to respond, so the
+                // interaction is always deferred, following the defer policy if any.
+                let ephemeral = defer.is_some_and(|defer| defer.ephemeral);
+                if let Err(why) = context.defer(ephemeral).await {
+                    debug!("Failed to defer queued command [{}]: {}", command, why);
+                }
+
+                Some(self.concurrency.acquire(&command, bucket, concurrency).await)
             }
         }
     }
 
+    /// Registers a use of the given command, returning the remaining time of its cooldown if it
+    /// can't be used yet.
+    async fn check_cooldown(&self, cmd: &Command<D, T, E>, interaction: &Interaction) -> Option<Duration> {
+        let cooldown = cmd.cooldown.as_ref()?;
+        let key = CooldownKey {
+            command: command_path(interaction),
+            bucket: Bucket::new(cooldown.bucket, interaction)
+        };
+
+        self.cooldown_storage.hit(&key, cooldown).await
+    }
+
     /// Registers the commands provided to the framework in the specified guild.
     pub async fn register_guild_commands(
         &self,
@@ -324,11 +882,11 @@ where
         for cmd in self.commands.values() {
             debug!("Registering command [{}]", cmd.name);
 
-            commands.push(cmd.create(&self, &self.interaction_client(), Some(guild_id)).await?);
+            commands.push(cmd.create(self, &self.interaction_client(), Some(guild_id)).await?);
         }
 
         for group in self.groups.values() {
-            commands.push(group.create(&self, &self.interaction_client(), Some(guild_id)).await?);
+            commands.push(group.create(self, &self.interaction_client(), Some(guild_id)).await?);
         }
 
         Ok(commands)
@@ -341,11 +899,11 @@ where
         let mut commands = Vec::new();
 
         for cmd in self.commands.values() {
-            commands.push(cmd.create(&self, &self.interaction_client(), None).await?);
+            commands.push(cmd.create(self, &self.interaction_client(), None).await?);
         }
 
         for group in self.groups.values() {
-            commands.push(group.create(&self, &self.interaction_client(), None).await?);
+            commands.push(group.create(self, &self.interaction_client(), None).await?);
         }
 
         Ok(commands)
@@ -353,6 +911,8 @@ where
 
     /// Creates a vector of Twilight [`Command`](twilight_model::application::command::Command) objects, to be used against Discord's bulk endpoint.
     #[cfg(feature = "bulk")]
+    // `dm_permission` is still used when creating the commands through the http client.
+    #[allow(deprecated)]
     pub fn twilight_commands(
         &self,
     ) -> Vec<TwilightCommand> {
@@ -383,6 +943,7 @@ where
             }
 
             if_some!(cmd.required_permissions, |p| command = command.default_member_permissions(p));
+            command = command.nsfw(cmd.nsfw).dm_permission(!cmd.only_guilds);
             //if_some!(&cmd.localized_names, |n| command = command.name_localizations(n));
             if let Some(localizations) = cmd.localized_names.get_localizations(self, cmd) {
                 command = command.name_localizations(localizations);
@@ -402,6 +963,7 @@ where
             }
 
             if_some!(group.required_permissions, |p| command = command.default_member_permissions(p));
+            command = command.nsfw(group.nsfw).dm_permission(!group.only_guilds);
 
             commands.push(command.build());
         }
@@ -409,3 +971,45 @@ where
         commands
     }
 }
+
+/// Joins the permissions required by a command and by its group.
+fn merge_permissions(command: Option<Permissions>, group: Option<Permissions>) -> Option<Permissions> {
+    match (command, group) {
+        (Some(command), Some(group)) => Some(command | group),
+        (command, group) => command.or(group)
+    }
+}
+
+/// Gets the required permissions not present in the available ones, taking into account that
+/// administrators have all permissions.
+fn missing_permissions(required: Permissions, available: Permissions) -> Permissions {
+    if available.contains(Permissions::ADMINISTRATOR) {
+        Permissions::empty()
+    } else {
+        required - available
+    }
+}
+
+/// Gets the full path of the command invoked by the given interaction, including its parent and
+/// group, separated by spaces.
+pub(crate) fn command_path(interaction: &Interaction) -> String {
+    let Some(InteractionData::ApplicationCommand(data)) = interaction.data.as_ref() else {
+        return String::new();
+    };
+
+    let mut path = data.name.clone();
+    let mut options = &data.options;
+
+    while let Some(option) = options.first() {
+        match &option.value {
+            CommandOptionValue::SubCommandGroup(next) | CommandOptionValue::SubCommand(next) => {
+                path.push(' ');
+                path.push_str(&option.name);
+                options = next;
+            }
+            _ => break
+        }
+    }
+
+    path
+}
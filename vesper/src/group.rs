@@ -6,6 +6,7 @@ use twilight_model::{
 
 use crate::{
     command::{Command, CommandMap},
+    middleware::Layers,
     prelude::{CreateCommandError, Framework},
     twilight_exports::{Command as TwilightCommand, Permissions},
 };
@@ -61,6 +62,10 @@ pub struct GroupParent<D, T, E> {
     pub kind: ParentType<D, T, E>,
     /// The required permissions to execute commands inside this group
     pub required_permissions: Option<Permissions>,
+    /// The permissions the bot needs to execute commands inside this group.
+    pub required_bot_permissions: Option<Permissions>,
+    /// The layers wrapping the execution of commands inside this group.
+    pub layers: Layers<D, T, E>,
     pub nsfw: bool,
     pub only_guilds: bool,
 }
@@ -1,32 +1,38 @@
+use crate::check::{CheckOutcome, Denial};
+use crate::command::CommandInfo;
 use crate::context::AutocompleteContext;
+use crate::error::FrameworkError;
 use crate::{
     context::SlashContext, twilight_exports::InteractionResponseData,
     BoxFuture,
 };
+use std::{sync::Arc, time::Duration};
 
 /// A pointer to a function used by [before hook](BeforeHook).
-pub(crate) type BeforeFn<D> = for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>, &'cx str) -> BoxFuture<'cx, bool>;
+pub(crate) type BeforeFn<D, T, E> = for<'cx, 'data, 'info> fn(&'cx mut SlashContext<'data, D>, &'cx CommandInfo<'info, D, T, E>) -> BoxFuture<'cx, bool>;
 /// A hook executed before a command execution.
 ///
-/// The function must have as parameters a [slash context] reference and a `&str`
-/// which contains the name of the command to execute.
+/// The function must have as parameters a [slash context] reference and a [CommandInfo]
+/// reference describing the command to execute.
 ///
 /// [slash context]: SlashContext
-pub struct BeforeHook<D>(pub BeforeFn<D>);
+pub struct BeforeHook<D, T, E>(pub BeforeFn<D, T, E>);
 
 /// A pointer to a function used by [after hook](AfterHook).
 pub(crate) type AfterFn<D, T, E> =
-    for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>, &'cx str, Option<Result<T, E>>) -> BoxFuture<'cx, ()>;
+    for<'cx, 'data, 'info> fn(&'cx mut SlashContext<'data, D>, &'cx CommandInfo<'info, D, T, E>, Option<Result<T, FrameworkError<E>>>, Option<Denial>) -> BoxFuture<'cx, ()>;
 
 /// A hook executed after a command execution.
 ///
-/// The function must have as parameters a [slash context] reference, a `&str` which contains
-/// the name of the command, and an `Option<Result<T, E>>`.
+/// The function must have as parameters a [slash context] reference, a [CommandInfo] reference
+/// describing the command, an `Option<Result<T, FrameworkError<E>>>` and optionally an
+/// `Option<Denial>`.
 ///
-/// The result contained in the option must be the same as your command's output.
+/// The `T` and `E` types contained in the result must be the same as your command's output.
 ///
-/// Note that it will be missing only if the command had an error and an error handler was set
-/// to handle the error.
+/// Note that it will be missing if the command had an error and an error handler was set
+/// to handle the error, or if a check denied the execution, in which case the [denial](Denial)
+/// is provided.
 ///
 /// [slash context]: SlashContext
 pub struct AfterHook<D, T, E>(pub AfterFn<D, T, E>);
@@ -40,20 +46,60 @@ pub(crate) type AutocompleteFn<D> =
 /// The function must have as parameter a single [autocomplete context](AutocompleteContext).
 pub struct AutocompleteHook<D>(pub AutocompleteFn<D>);
 
-/// A pointer to a function used by the [check hook](CheckHook).
-pub(crate) type CheckFn<D, E> = for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>) -> BoxFuture<'cx, Result<bool, E>>;
+/// A function used by the [check hook](CheckHook).
+pub(crate) type CheckFn<D, E> = dyn for<'cx, 'data> Fn(&'cx mut SlashContext<'data, D>) -> BoxFuture<'cx, Result<CheckOutcome, E>>
+    + Send
+    + Sync;
 
 /// A hook that can be used to determine if a command should execute or not depending
 /// on the given function.
-pub struct CheckHook<D, E>(pub CheckFn<D, E>);
+///
+/// The function can return either a `bool` or a [CheckOutcome], which allows giving the reason
+/// the execution was denied.
+///
+/// Unlike other hooks, checks can capture state, which allows creating them from closures, see
+/// the [prebuilt checks](crate::checks).
+pub struct CheckHook<D, E>(pub Arc<CheckFn<D, E>>);
+
+impl<D, E> CheckHook<D, E> {
+    /// Creates a new check from the given function.
+    pub fn new<F>(fun: F) -> Self
+    where
+        F: for<'cx, 'data> Fn(&'cx mut SlashContext<'data, D>) -> BoxFuture<'cx, Result<CheckOutcome, E>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(Arc::new(fun))
+    }
+}
+
+impl<D, E> Clone for CheckHook<D, E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
 
 /// A pointer to a function used by the [error handler hook](ErrorHandlerHook).
-pub(crate) type ErrorHandlerFn<D, E> = for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>, E) -> BoxFuture<'cx, ()>;
+pub(crate) type ErrorHandlerFn<D, T, E> =
+    for<'cx, 'data, 'info> fn(&'cx mut SlashContext<'data, D>, &'cx CommandInfo<'info, D, T, E>, FrameworkError<E>) -> BoxFuture<'cx, ()>;
 
 /// A hook that can be used to handle errors of an specific command and its checks.
 ///
-/// The function must have as parameters a [slash context] reference and the actual error type
+/// The function must have as parameters a [slash context] reference, a [CommandInfo] reference
+/// describing the command that failed and a [FrameworkError] containing the actual error type
 /// the function and check is supposed to return.
 ///
 /// [slash context]: SlashContext
-pub struct ErrorHandlerHook<D, E>(pub ErrorHandlerFn<D, E>);
+pub struct ErrorHandlerHook<D, T, E>(pub ErrorHandlerFn<D, T, E>);
+
+/// A pointer to a function used by the [cooldown hook](CooldownHook).
+pub(crate) type CooldownFn<D> = for<'cx, 'data> fn(&'cx mut SlashContext<'data, D>, Duration) -> BoxFuture<'cx, ()>;
+
+/// A hook used to tell the user a command is on [cooldown](crate::cooldown::Cooldown).
+///
+/// The function must have as parameters a [slash context] reference and a [Duration] containing
+/// the remaining time until the command can be used again.
+///
+/// [slash context]: SlashContext
+pub struct CooldownHook<D>(pub CooldownFn<D>);
@@ -1,32 +1,45 @@
-use crate::builder::WrappedClient;
 use crate::context::SlashContext;
-use crate::parse::{Parse, ParseError};
+use crate::parse::{Parse, ParseContext, ParseError};
 use crate::twilight_exports::{
-    CommandDataOption, CommandOptionType, CommandOptionValue, InteractionData,
-    InteractionDataResolved,
+    CommandDataOption, CommandOptionType, CommandOptionValue, CommandType, Id, InteractionData,
+    InteractionDataResolved, UserMarker,
 };
 
 /// An iterator used to iterate through slash command options.
 pub struct DataIterator<'a, D> {
     src: Vec<&'a CommandDataOption>,
-    resolved: &'a mut Option<InteractionDataResolved>,
-    http: &'a WrappedClient,
-    data: &'a D,
+    target: Option<Id<UserMarker>>,
+    context: ParseContext<'a, D>,
 }
 
 impl<'a, D> DataIterator<'a, D> {
     /// Creates a new [iterator](self::DataIterator) at the given source.
     pub fn new(ctx: &'a mut SlashContext<'_, D>) -> Self {
-        let data = match ctx.interaction.data.as_mut().unwrap() {
+        let author_id = ctx.interaction.author_id();
+        let channel_id = ctx.interaction.channel.as_ref().map(|channel| channel.id);
+        let interaction = &mut ctx.interaction;
+
+        let data = match interaction.data.as_mut().unwrap() {
             InteractionData::ApplicationCommand(data) => data,
             _ => unreachable!(),
         };
 
+        let target = match data.kind {
+            CommandType::User => data.target_id.map(Id::cast),
+            _ => None,
+        };
+
         Self {
             src: Self::get_data(&data.options),
-            resolved: &mut data.resolved,
-            http: ctx.http_client,
-            data: ctx.data,
+            target,
+            context: ParseContext {
+                guild_id: interaction.guild_id,
+                channel_id,
+                author_id,
+                locale: interaction.locale.as_deref(),
+                guild_locale: interaction.guild_locale.as_deref(),
+                ..ParseContext::new(ctx.http_client, ctx.data, data.resolved.as_mut())
+            },
         }
     }
 }
@@ -66,10 +79,10 @@ impl<'a, D: 'a> DataIterator<'a, D> {
     }
 
     pub fn resolved(&mut self) -> Option<&mut InteractionDataResolved> {
-        self.resolved.as_mut()
+        self.context.resolved()
     }
 
-    fn get_data(options: &Vec<CommandDataOption>) -> Vec<&CommandDataOption> {
+    fn get_data(options: &[CommandDataOption]) -> Vec<&CommandDataOption> {
         if let Some(index) = options.iter().position(|item| {
             item.value.kind() == CommandOptionType::SubCommand
                 || item.value.kind() == CommandOptionType::SubCommandGroup
@@ -93,26 +106,38 @@ where
 {
     pub async fn named_parse<T>(&mut self, name: &str) -> Result<T, ParseError>
     where
-        T: Parse<D>,
+        T: Parse<D> + Send,
     {
         let value = self.get(|s| s.name == name);
         if value.is_none() && <T as Parse<D>>::required() {
-            Err(ParseError::StructureMismatch(format!("{} not found", name)).into())
+            Err(ParseError::StructureMismatch(format!("{} not found", name)))
         } else {
-            Ok(T::parse(
-                self.http,
-                self.data,
-                value.map(|it| &it.value),
-                self.resolved(),
-            )
+            Ok(T::parse_with(&mut self.context, value.map(|it| &it.value))
+                .await
+                .map_err(|mut err| {
+                    if let ParseError::Parsing { argument_name, .. } = &mut err {
+                        *argument_name = name.to_string();
+                    }
+                    err
+                })?)
+        }
+    }
+
+    /// Parses the target of a user command, as if it was given as a user argument.
+    pub async fn target_parse<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: Parse<D> + Send,
+    {
+        let value = self.target.map(CommandOptionValue::User);
+
+        T::parse_with(&mut self.context, value.as_ref())
             .await
             .map_err(|mut err| {
                 if let ParseError::Parsing { argument_name, .. } = &mut err {
-                    *argument_name = name.to_string();
+                    *argument_name = "target".to_string();
                 }
                 err
-            })?)
-        }
+            })
     }
 }
 
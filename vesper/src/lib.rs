@@ -1,21 +1,43 @@
-#![doc = include_str!("../README.md")]
+// The examples of the readme use placeholders for the types of each bot, so they are not
+// compiled as doctests.
+#![cfg_attr(not(doctest), doc = include_str!("../README.md"))]
 
+mod dispatch;
 mod parse_impl;
+mod unwind;
 
 pub mod argument;
 pub mod builder;
+pub mod check;
+pub mod checks;
+pub mod collector;
 pub mod command;
+pub mod component;
+pub mod concurrency;
 pub mod context;
+pub mod cooldown;
+pub mod custom_id;
+pub mod defer;
+#[cfg(feature = "endpoint")]
+pub mod endpoint;
 pub mod error;
+pub mod error_response;
+pub mod extensions;
 pub mod framework;
 pub mod group;
 pub mod hook;
 pub mod iter;
 pub mod localizations;
+pub mod middleware;
 pub mod modal;
+pub mod paginator;
 pub mod parse;
 pub mod parsers;
 pub mod range;
+pub mod response;
+pub mod string;
+#[cfg(feature = "bulk")]
+pub mod sync;
 pub mod wait;
 
 // Items used to extract generics from functions, not public API.
@@ -30,13 +52,20 @@ type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> +
 pub mod prelude {
     pub use crate::{
         builder::{FrameworkBuilder, WrappedClient},
+        check::{CheckOutcome, Denial},
+        command::CommandInfo,
         context::{AutocompleteContext, Focused, SlashContext},
+        custom_id::CustomId,
+        defer::Defer,
         error::*,
-        framework::{DefaultCommandResult, Framework},
+        framework::{DefaultCommandResult, DefaultFrameworkResult, Framework},
         modal::*,
-        parse::{Parse, ParseError},
+        paginator::{Page, PageSource},
+        parse::{Parse, ParseContext, ParseError},
         parsers,
-        range::Range,
+        range::{FloatRange, Max, Min, Range},
+        response::{Reply, ResponseState},
+        string::{Length, Validated, Validator},
     };
     pub use async_trait::async_trait;
     pub use vesper_macros::*;
@@ -0,0 +1,143 @@
+//! Layers wrapping the execution of commands.
+//!
+//! A [layer](Layer) receives the context of the invocation and a [next](Next) handle running
+//! the rest of the stack, so it can run code before and after the execution, retry it or skip it
+//! entirely:
+//!
+//! ```rust
+//! use std::time::Instant;
+//! use vesper::prelude::*;
+//! use vesper::command::ExecutionResult;
+//! use vesper::middleware::{Layer, Next};
+//!
+//! struct Timing;
+//!
+//! #[async_trait]
+//! impl<D: Sync, T: Send, E: Send> Layer<D, T, E> for Timing {
+//!     async fn call(
+//!         &self,
+//!         context: &mut SlashContext<'_, D>,
+//!         next: Next<'_, D, T, E>
+//!     ) -> ExecutionResult<T, E> {
+//!         let start = Instant::now();
+//!         let result = next.run(context).await;
+//!         println!("[{}] took {:?}", next.command().name, start.elapsed());
+//!         result
+//!     }
+//! }
+//! ```
+//!
+//! Layers can pass data to the command through the [extensions](crate::extensions::Extensions)
+//! of the context:
+//!
+//! ```rust
+//! use vesper::prelude::*;
+//! use vesper::command::ExecutionResult;
+//! use vesper::middleware::{Layer, Next};
+//!
+//! struct RequestId(u64);
+//!
+//! struct Tagging;
+//!
+//! #[async_trait]
+//! impl<D: Sync, T: Send, E: Send> Layer<D, T, E> for Tagging {
+//!     async fn call(
+//!         &self,
+//!         context: &mut SlashContext<'_, D>,
+//!         next: Next<'_, D, T, E>
+//!     ) -> ExecutionResult<T, E> {
+//!         context.extensions.insert(RequestId(context.interaction.id.get()));
+//!         next.run(context).await
+//!     }
+//! }
+//!
+//! #[command]
+//! #[description = "Shows the id of the request"]
+//! #[layers(Tagging)]
+//! async fn request(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+//!     let id = ctx.extensions.get::<RequestId>().map(|id| id.0).unwrap_or_default();
+//!     ctx.reply(format!("Request {id}")).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    command::{Command, CommandInfo, ExecutionResult},
+    context::SlashContext,
+    framework::Framework,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A list of [layers](Layer), ordered from the outermost to the innermost one.
+pub type Layers<D, T, E> = Vec<Arc<dyn Layer<D, T, E>>>;
+
+/// A middleware wrapping the execution of commands.
+///
+/// Layers of the framework wrap the ones of groups, which wrap the ones of commands. They only
+/// run once the permissions, concurrency limit, checks and cooldown of the command allowed the
+/// execution, and the innermost layer wraps the command itself. The error handler, the denial
+/// response and the `after` hook run once the whole stack returns.
+///
+/// Layers only wrap commands, [component handlers](crate::component::ComponentHandler) are
+/// executed without them.
+#[async_trait]
+pub trait Layer<D, T, E>: Send + Sync {
+    /// Handles an invocation, calling [next](Next::run) to continue the execution. Returning
+    /// without calling it stops the execution with the returned result.
+    async fn call(
+        &self,
+        context: &mut SlashContext<'_, D>,
+        next: Next<'_, D, T, E>
+    ) -> ExecutionResult<T, E>;
+}
+
+/// The rest of the layer stack of an invocation, ending with the execution of the command.
+///
+/// It can be [run](Self::run) more than once, for example to retry a failed execution, running
+/// the rest of the stack and the command again each time. The permissions, concurrency limit,
+/// checks and cooldown of the command are enforced once before the stack, so they don't apply to
+/// the repeated runs.
+pub struct Next<'a, D, T, E> {
+    framework: &'a Framework<D, T, E>,
+    command: &'a Command<D, T, E>,
+    info: &'a CommandInfo<'a, D, T, E>,
+    layers: &'a [&'a dyn Layer<D, T, E>],
+}
+
+impl<'a, D, T, E> Next<'a, D, T, E> {
+    pub(crate) fn new(
+        framework: &'a Framework<D, T, E>,
+        command: &'a Command<D, T, E>,
+        info: &'a CommandInfo<'a, D, T, E>,
+        layers: &'a [&'a dyn Layer<D, T, E>],
+    ) -> Self {
+        Self {
+            framework,
+            command,
+            info,
+            layers,
+        }
+    }
+
+    /// The command being executed.
+    pub fn command(&self) -> &'a Command<D, T, E> {
+        self.command
+    }
+
+    /// The information about the invocation, including the full path of the command.
+    pub fn info(&self) -> &'a CommandInfo<'a, D, T, E> {
+        self.info
+    }
+
+    /// Runs the remaining layers and the command, returning the result of the execution.
+    ///
+    /// The response state of the interaction is kept across runs, so a repeated run edits or
+    /// follows up the responses of the previous ones.
+    pub async fn run(&self, context: &mut SlashContext<'_, D>) -> ExecutionResult<T, E> {
+        match self.layers.split_first() {
+            Some((layer, layers)) => layer.call(context, Self { layers, ..*self }).await,
+            None => self.framework.run_command(self.command, self.info, context).await,
+        }
+    }
+}
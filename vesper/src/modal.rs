@@ -3,10 +3,11 @@ use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll, ready};
 use thiserror::Error;
-use tokio::sync::oneshot::error::RecvError;
+use std::time::Duration;
 use twilight_model::channel::message::MessageFlags;
 use crate::context::SlashContext;
-use crate::wait::InteractionWaiter;
+use crate::custom_id::CustomIdError;
+use crate::wait::{InteractionWaiter, WaitError};
 use crate::twilight_exports::{Interaction, InteractionClient, InteractionResponse, InteractionResponseType, InteractionResponseData};
 use std::fmt::{Debug, Formatter};
 use twilight_http::response::marker::EmptyBody;
@@ -19,8 +20,10 @@ use twilight_http::response::ResponseFuture;
 pub enum ModalError {
     /// An http error occurred.
     Http(#[from] twilight_http::Error),
-    /// Something failed when using a [waiter](InteractionWaiter)
-    Waiter(#[from] RecvError)
+    /// Something failed when using a [waiter](InteractionWaiter), or it timed out.
+    Waiter(#[from] WaitError),
+    /// The custom id of the modal could not be encoded.
+    CustomId(#[from] CustomIdError)
 }
 
 /// The outcome of `.await`ing a [WaitModal](WaitModal).
@@ -105,6 +108,12 @@ impl<'ctx, S> WaitModal<'ctx, S> {
         self
     }
 
+    /// Sets the maximum time to wait for the user to submit the modal, after which the waiter
+    /// resolves to a [timeout error](WaitError::Timeout).
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.waiter = self.waiter.map(|waiter| waiter.timeout(duration));
+        self
+    }
 
 }
 
@@ -167,7 +176,8 @@ pub trait Modal<D> {
     ///
     /// The framework provides as a custom id the interaction id converted to a string, this custom
     /// id must be used as the response custom id in order for the framework to retrieve the modal
-    /// data.
+    /// data. When the modal is created using [SlashContext::show_modal], the custom id is the
+    /// encoded [state](crate::custom_id::CustomId) instead.
     fn create(ctx: &SlashContext<'_, D>, custom_id: String) -> InteractionResponse;
     /// Parses the provided interaction into the modal;
     fn parse(interaction: &mut Interaction) -> Self;
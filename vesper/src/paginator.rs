@@ -0,0 +1,414 @@
+use crate::{
+    context::SlashContext,
+    response::{Reply, ResponseError, ResponseState},
+    twilight_exports::{
+        ActionRow, Component, Interaction, InteractionData, InteractionResponse,
+        InteractionResponseData, InteractionResponseType, TextInput, TextInputStyle,
+    },
+};
+use async_trait::async_trait;
+use futures_core::Stream;
+use std::{future::poll_fn, pin::Pin, time::Duration};
+use thiserror::Error;
+use twilight_model::channel::message::{
+    component::{Button, ButtonStyle},
+    Embed, MessageFlags,
+};
+
+/// The time the paginator waits for an interaction before disabling its buttons.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The prefix of the custom ids used by the paginator buttons.
+const CUSTOM_ID_PREFIX: &str = "vesper-paginator";
+
+/// Errors that can occur when running a [paginator](Paginator).
+#[derive(Debug, Error)]
+pub enum PaginatorError {
+    /// An http error occurred.
+    #[error(transparent)]
+    Http(#[from] twilight_http::Error),
+    /// The paginator could not be sent.
+    #[error(transparent)]
+    Response(#[from] ResponseError),
+    /// The page source does not have any page.
+    #[error("The paginator does not have any page")]
+    Empty,
+}
+
+/// A page shown by the [paginator](Paginator).
+#[derive(Clone, Debug, Default)]
+pub struct Page {
+    /// The content of the message.
+    pub content: Option<String>,
+    /// The embeds of the message.
+    pub embeds: Vec<Embed>,
+}
+
+impl From<String> for Page {
+    fn from(content: String) -> Self {
+        Self {
+            content: Some(content),
+            embeds: Vec::new(),
+        }
+    }
+}
+
+impl From<&str> for Page {
+    fn from(content: &str) -> Self {
+        Self::from(content.to_string())
+    }
+}
+
+impl From<Embed> for Page {
+    fn from(embed: Embed) -> Self {
+        Self {
+            content: None,
+            embeds: vec![embed],
+        }
+    }
+}
+
+impl From<Vec<Embed>> for Page {
+    fn from(embeds: Vec<Embed>) -> Self {
+        Self {
+            content: None,
+            embeds,
+        }
+    }
+}
+
+/// A source of the pages shown by the [paginator](Paginator).
+///
+/// This trait is implemented for vectors of pages, and can be implemented to create the pages
+/// lazily, for example fetching them from a database only when they are shown.
+#[async_trait]
+pub trait PageSource: Send + Sync {
+    /// Returns the amount of pages available.
+    fn len(&self) -> usize;
+
+    /// Returns whether the source does not have any page.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Creates the page at the given index, which is always lower than [len](Self::len).
+    async fn page(&self, index: usize) -> Page;
+}
+
+#[async_trait]
+impl<P: Into<Page> + Clone + Send + Sync> PageSource for Vec<P> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    async fn page(&self, index: usize) -> Page {
+        self[index].clone().into()
+    }
+}
+
+/// The controls of the paginator.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Control {
+    First,
+    Previous,
+    Jump,
+    Next,
+    Last,
+}
+
+impl Control {
+    const ALL: [Self; 5] = [Self::First, Self::Previous, Self::Jump, Self::Next, Self::Last];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::First => "first",
+            Self::Previous => "prev",
+            Self::Jump => "jump",
+            Self::Next => "next",
+            Self::Last => "last",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|control| control.name() == name)
+    }
+}
+
+/// A paginator showing a set of pages, obtained using [SlashContext::paginate].
+///
+/// The pages are sent as the response of the interaction with buttons to go to the first,
+/// previous, next and last pages, and a button showing the current page which allows jumping to
+/// any page. Only the user who invoked the command can use the buttons, and they are disabled
+/// once no button is used during the [timeout](Self::timeout).
+///
+/// # Examples
+///
+/// ```rust
+/// use vesper::prelude::*;
+///
+/// #[command]
+/// #[description = "Shows some pages"]
+/// async fn pages(ctx: &mut SlashContext<()>) -> DefaultCommandResult {
+///     ctx.paginate(vec!["First page", "Second page", "Third page"])
+///         .ephemeral()
+///         .run()
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[must_use = "Paginators do nothing unless run"]
+pub struct Paginator<'ctx, 'data, D> {
+    ctx: &'ctx SlashContext<'data, D>,
+    source: Box<dyn PageSource + 'ctx>,
+    index: usize,
+    ephemeral: bool,
+    timeout: Duration,
+}
+
+impl<'ctx, 'data, D> Paginator<'ctx, 'data, D> {
+    pub(crate) fn new(ctx: &'ctx SlashContext<'data, D>, source: impl PageSource + 'ctx) -> Self {
+        Self {
+            ctx,
+            source: Box::new(source),
+            index: 0,
+            ephemeral: false,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sends the pages as an ephemeral message.
+    pub fn ephemeral(mut self) -> Self {
+        self.ephemeral = true;
+        self
+    }
+
+    /// Sets the time to wait for a button to be used before disabling them, by default two
+    /// minutes. The timeout restarts every time a button is used.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = duration;
+        self
+    }
+
+    /// Sets the page shown first, by default the first one.
+    pub fn start_at(mut self, index: usize) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Sends the pages and handles the buttons until the paginator times out.
+    pub async fn run(mut self) -> Result<(), PaginatorError> {
+        if self.source.is_empty() {
+            return Err(PaginatorError::Empty);
+        }
+
+        let invoker = self.ctx.interaction.author_id();
+        let prefix = format!("{}:{}:", CUSTOM_ID_PREFIX, self.ctx.interaction.id);
+        self.index = self.index.min(self.source.len() - 1);
+
+        let mut reply = self.render(&prefix, false).await;
+        reply.ephemeral = self.ephemeral;
+
+        // When the interaction was already responded the pages are sent as a followup message,
+        // which must be edited through its id once the paginator times out.
+        let followup = match self.ctx.response_state() {
+            ResponseState::Responded => Some(self.ctx.followup(reply).await?.id),
+            _ => {
+                self.ctx.reply(reply).await?;
+                None
+            }
+        };
+
+        let mut collector = self
+            .ctx
+            .collect_components()
+            .custom_id_prefix(prefix.clone())
+            .idle_timeout(self.timeout)
+            .build();
+
+        while let Some(interaction) = poll_fn(|cx| Pin::new(&mut collector).poll_next(cx)).await {
+            if interaction.author_id() != invoker {
+                collector
+                    .respond(&interaction, &ephemeral_message("You can't use these buttons"))
+                    .await?;
+                continue;
+            }
+
+            let control = custom_id(&interaction)
+                .and_then(|id| id.strip_prefix(prefix.as_str()))
+                .and_then(Control::from_name);
+
+            let (interaction, index) = match control {
+                Some(Control::First) => (interaction, 0),
+                Some(Control::Previous) => (interaction, self.index.saturating_sub(1)),
+                Some(Control::Next) => (interaction, (self.index + 1).min(self.source.len() - 1)),
+                Some(Control::Last) => (interaction, self.source.len() - 1),
+                Some(Control::Jump) => match self.ask_page(&collector, &prefix, interaction).await? {
+                    Some(result) => result,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            self.index = index;
+            let data = self.render(&prefix, false).await.into_data();
+            collector
+                .respond(&interaction, &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(data),
+                })
+                .await?;
+        }
+
+        let components = self.render(&prefix, true).await.components;
+        let token = &self.ctx.interaction.token;
+
+        match followup {
+            Some(message) => {
+                self.ctx
+                    .interaction_client
+                    .update_followup(token, message)
+                    .components(Some(&components))
+                    .await?;
+            }
+            None => {
+                self.ctx
+                    .interaction_client
+                    .update_response(token)
+                    .components(Some(&components))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shows a modal asking for the page to jump to, returning the modal submit interaction and
+    /// the selected page index.
+    async fn ask_page(
+        &self,
+        collector: &crate::collector::ComponentCollector<'_>,
+        prefix: &str,
+        interaction: Interaction,
+    ) -> Result<Option<(Interaction, usize)>, PaginatorError> {
+        let modal_id = format!("{}modal:{}", prefix, interaction.id);
+
+        collector
+            .respond(&interaction, &InteractionResponse {
+                kind: InteractionResponseType::Modal,
+                data: Some(InteractionResponseData {
+                    custom_id: Some(modal_id.clone()),
+                    title: Some(String::from("Jump to page")),
+                    components: Some(vec![Component::ActionRow(ActionRow {
+                        components: vec![Component::TextInput(TextInput {
+                            custom_id: String::from("page"),
+                            label: format!("Page (1 - {})", self.source.len()),
+                            placeholder: None,
+                            style: TextInputStyle::Short,
+                            max_length: Some(10),
+                            min_length: Some(1),
+                            required: Some(true),
+                            value: None,
+                        })],
+                    })]),
+                    ..Default::default()
+                }),
+            })
+            .await?;
+
+        let waiter = self
+            .ctx
+            .wait_interaction(move |interaction| custom_id(interaction) == Some(modal_id.as_str()))
+            .timeout(self.timeout);
+
+        let Ok(mut submit) = waiter.await else {
+            return Ok(None);
+        };
+
+        let page = modal_value(&mut submit)
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|page| (1..=self.source.len()).contains(page));
+
+        match page {
+            Some(page) => Ok(Some((submit, page - 1))),
+            None => {
+                collector
+                    .respond(&submit, &ephemeral_message("That page does not exist"))
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Renders the current page along with the buttons.
+    async fn render(&self, prefix: &str, disabled: bool) -> Reply {
+        let page = self.source.page(self.index).await;
+        let last = self.source.len() - 1;
+
+        let buttons = Control::ALL
+            .iter()
+            .copied()
+            .map(|control| {
+                let (label, at_limit) = match control {
+                    Control::First => (String::from("⏮"), self.index == 0),
+                    Control::Previous => (String::from("◀"), self.index == 0),
+                    Control::Jump => (format!("{} / {}", self.index + 1, last + 1), last == 0),
+                    Control::Next => (String::from("▶"), self.index == last),
+                    Control::Last => (String::from("⏭"), self.index == last),
+                };
+
+                Component::Button(Button {
+                    custom_id: Some(format!("{}{}", prefix, control.name())),
+                    disabled: disabled || at_limit,
+                    emoji: None,
+                    label: Some(label),
+                    style: if control == Control::Jump {
+                        ButtonStyle::Primary
+                    } else {
+                        ButtonStyle::Secondary
+                    },
+                    url: None,
+                    sku_id: None,
+                })
+            })
+            .collect();
+
+        Reply {
+            content: page.content,
+            embeds: page.embeds,
+            components: vec![Component::ActionRow(ActionRow { components: buttons })],
+            ephemeral: false,
+        }
+    }
+}
+
+fn custom_id(interaction: &Interaction) -> Option<&str> {
+    match interaction.data.as_ref()? {
+        InteractionData::MessageComponent(data) => Some(&data.custom_id),
+        InteractionData::ModalSubmit(data) => Some(&data.custom_id),
+        _ => None,
+    }
+}
+
+fn modal_value(interaction: &mut Interaction) -> Option<String> {
+    let Some(InteractionData::ModalSubmit(data)) = &mut interaction.data else {
+        return None;
+    };
+
+    data.components
+        .iter_mut()
+        .flat_map(|row| row.components.iter_mut())
+        .find(|component| component.custom_id == "page")
+        .and_then(|component| component.value.take())
+}
+
+fn ephemeral_message(content: &str) -> InteractionResponse {
+    InteractionResponse {
+        kind: InteractionResponseType::ChannelMessageWithSource,
+        data: Some(InteractionResponseData {
+            content: Some(content.to_string()),
+            flags: Some(MessageFlags::EPHEMERAL),
+            ..Default::default()
+        }),
+    }
+}
@@ -1,17 +1,101 @@
 use crate::{builder::WrappedClient, twilight_exports::*};
 use async_trait::async_trait;
 use std::error::Error;
+use std::any::type_name;
 
-/// The core trait of this framework, it is used to parse all command arguments
+/// The context of the invocation an argument is parsed for, given to [Parse] implementations.
+pub struct ParseContext<'a, T> {
+    /// The http client used by the framework.
+    pub http_client: &'a WrappedClient,
+    /// The data shared across the framework.
+    pub data: &'a T,
+    /// The resolved data of the interaction, containing the users, roles, channels and
+    /// attachments given as arguments.
+    pub resolved: Option<&'a mut InteractionDataResolved>,
+    /// The guild the command was used in, `None` in direct messages.
+    pub guild_id: Option<Id<GuildMarker>>,
+    /// The channel the command was used in.
+    pub channel_id: Option<Id<ChannelMarker>>,
+    /// The user who used the command.
+    pub author_id: Option<Id<UserMarker>>,
+    /// The locale of the user who used the command.
+    pub locale: Option<&'a str>,
+    /// The preferred locale of the guild the command was used in.
+    pub guild_locale: Option<&'a str>,
+    /// The type whose default [parse](Parse::parse) created this context, used to detect types
+    /// implementing neither parsing method.
+    pub(crate) delegated_by: Option<&'static str>,
+}
+
+impl<'a, T> ParseContext<'a, T> {
+    /// Creates a new context without any information about the interaction.
+    pub fn new(
+        http_client: &'a WrappedClient,
+        data: &'a T,
+        resolved: Option<&'a mut InteractionDataResolved>,
+    ) -> Self {
+        Self {
+            http_client,
+            data,
+            resolved,
+            guild_id: None,
+            channel_id: None,
+            author_id: None,
+            locale: None,
+            guild_locale: None,
+            delegated_by: None,
+        }
+    }
+
+    /// Gets the resolved data of the interaction.
+    pub fn resolved(&mut self) -> Option<&mut InteractionDataResolved> {
+        self.resolved.as_deref_mut()
+    }
+}
+
+/// The core trait of this framework, it is used to parse all command arguments.
+///
+/// Implementations must override at least one of [parse_with](Self::parse_with) and
+/// [parse](Self::parse), as each one delegates to the other by default. Implementing
+/// `parse_with` gives access to the whole [context](ParseContext) of the invocation, while
+/// implementations written before it existed keep implementing `parse`. Types overriding neither
+/// fail to parse with [ParseError::Other].
 #[async_trait]
 pub trait Parse<T: Send + Sync>: Sized {
-    /// Parses the option into the argument.
+    /// Parses the option into the argument, with access to the context of the invocation.
+    ///
+    /// By default, this calls [parse](Self::parse) with the http client, the data and the
+    /// resolved data of the context.
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        // The context was created by the default `parse` of this same type, so neither method
+        // is implemented and delegating again would never end.
+        if ctx.delegated_by == Some(type_name::<Self>()) {
+            return Err(ParseError::Other(
+                format!("{} implements neither parse nor parse_with", type_name::<Self>()).into(),
+            ));
+        }
+
+        Self::parse(ctx.http_client, ctx.data, value, ctx.resolved()).await
+    }
+
+    /// Parses the option into the argument, without any information about the interaction.
+    ///
+    /// By default, this calls [parse_with](Self::parse_with) with a context only containing
+    /// the given items.
     async fn parse(
-        _http_client: &WrappedClient,
-        _data: &T,
-        _value: Option<&CommandOptionValue>,
-        _resolved: Option<&mut InteractionDataResolved>,
-    ) -> Result<Self, ParseError>;
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+        resolved: Option<&mut InteractionDataResolved>,
+    ) -> Result<Self, ParseError> {
+        let mut ctx = ParseContext::new(http_client, data, resolved);
+        ctx.delegated_by = Some(type_name::<Self>());
+
+        Self::parse_with(&mut ctx, value).await
+    }
 
     /// Returns the option type this argument has.
     fn kind() -> CommandOptionType;
@@ -92,3 +176,92 @@ impl From<&'static str> for ParseError {
         Self::StructureMismatch(why.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Implemented the way it was before `parse_with` existed.
+    struct Legacy(i64);
+
+    #[async_trait]
+    impl<T: Send + Sync> Parse<T> for Legacy {
+        async fn parse(
+            _: &WrappedClient,
+            _: &T,
+            value: Option<&CommandOptionValue>,
+            _: Option<&mut InteractionDataResolved>,
+        ) -> Result<Self, ParseError> {
+            match value {
+                Some(CommandOptionValue::Integer(number)) => Ok(Self(*number)),
+                _ => Err(ParseError::StructureMismatch(String::from("Integer expected"))),
+            }
+        }
+
+        fn kind() -> CommandOptionType {
+            CommandOptionType::Integer
+        }
+    }
+
+    /// Only implements `parse_with`, parsing through a type implementing `parse`.
+    struct Doubled(i64);
+
+    #[async_trait]
+    impl<T: Send + Sync> Parse<T> for Doubled {
+        async fn parse_with(
+            ctx: &mut ParseContext<'_, T>,
+            value: Option<&CommandOptionValue>,
+        ) -> Result<Self, ParseError> {
+            Legacy::parse_with(ctx, value).await.map(|Legacy(number)| Self(number * 2))
+        }
+
+        fn kind() -> CommandOptionType {
+            CommandOptionType::Integer
+        }
+    }
+
+    struct Unimplemented;
+
+    #[async_trait]
+    impl<T: Send + Sync> Parse<T> for Unimplemented {
+        fn kind() -> CommandOptionType {
+            CommandOptionType::Integer
+        }
+    }
+
+    fn client() -> WrappedClient {
+        Client::new(String::new()).into()
+    }
+
+    #[tokio::test]
+    async fn parse_with_delegates_to_parse() {
+        let client = client();
+        let mut ctx = ParseContext::new(&client, &(), None);
+        let value = CommandOptionValue::Integer(2);
+
+        let parsed = Legacy::parse_with(&mut ctx, Some(&value)).await.unwrap();
+        assert_eq!(parsed.0, 2);
+    }
+
+    #[tokio::test]
+    async fn parse_delegates_to_parse_with() {
+        let client = client();
+        let value = CommandOptionValue::Integer(2);
+
+        let parsed = Doubled::parse(&client, &(), Some(&value), None).await.unwrap();
+        assert_eq!(parsed.0, 4);
+    }
+
+    #[tokio::test]
+    async fn types_implementing_neither_method_fail() {
+        let client = client();
+        let value = CommandOptionValue::Integer(2);
+
+        let parsed = Unimplemented::parse(&client, &(), Some(&value), None).await;
+        assert!(matches!(parsed, Err(ParseError::Other(_))));
+
+        let mut ctx = ParseContext::new(&client, &(), None);
+        let parsed = Unimplemented::parse_with(&mut ctx, Some(&value)).await;
+        assert!(matches!(parsed, Err(ParseError::Other(_))));
+    }
+}
@@ -17,11 +17,9 @@ pub(crate) fn error(type_name: &str, required: bool, why: &str) -> ParseError {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for String {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::String(s)) = value {
             return Ok(s.to_owned());
@@ -36,11 +34,9 @@ impl<T: Send + Sync> Parse<T> for String {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for i64 {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Integer(i)) = value {
             return Ok(*i);
@@ -55,11 +51,9 @@ impl<T: Send + Sync> Parse<T> for i64 {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for u64 {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Integer(i)) = value {
             if *i < 0 {
@@ -82,11 +76,9 @@ impl<T: Send + Sync> Parse<T> for u64 {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for f64 {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Number(i)) = value {
             return Ok(*i);
@@ -107,11 +99,9 @@ impl<T: Send + Sync> Parse<T> for f64 {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for f32 {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Number(i)) = value {
             if *i > f32::MAX as f64 || *i < f32::MIN as f64 {
@@ -135,11 +125,9 @@ impl<T: Send + Sync> Parse<T> for f32 {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for bool {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Boolean(i)) = value {
             return Ok(*i);
@@ -154,11 +142,9 @@ impl<T: Send + Sync> Parse<T> for bool {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Id<AttachmentMarker> {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Attachment(attachment)) = value {
             return Ok(*attachment);
@@ -174,17 +160,14 @@ impl<T: Send + Sync> Parse<T> for Id<AttachmentMarker> {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Attachment {
-    async fn parse(
-        http_client: &WrappedClient,
-        data: &T,
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        resolved: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
-        let id = <Id<AttachmentMarker> as Parse<T>>::parse(http_client, data, value, None).await?;
+        let id = <Id<AttachmentMarker> as Parse<T>>::parse_with(ctx, value).await?;
 
-        resolved
-            .map(|item| item.attachments.remove(&id))
-            .flatten()
+        ctx.resolved()
+            .and_then(|item| item.attachments.remove(&id))
             .ok_or_else(|| error("Attachment", true, "Attachment expected"))
     }
 
@@ -195,11 +178,9 @@ impl<T: Send + Sync> Parse<T> for Attachment {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Id<ChannelMarker> {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Channel(channel)) = value {
             return Ok(*channel);
@@ -215,11 +196,9 @@ impl<T: Send + Sync> Parse<T> for Id<ChannelMarker> {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Id<UserMarker> {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::User(user)) = value {
             return Ok(*user);
@@ -235,17 +214,14 @@ impl<T: Send + Sync> Parse<T> for Id<UserMarker> {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for User {
-    async fn parse(
-        http_client: &WrappedClient,
-        data: &T,
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        resolved: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
-        let id = <Id<UserMarker> as Parse<T>>::parse(http_client, data, value, None).await?;
+        let id = <Id<UserMarker> as Parse<T>>::parse_with(ctx, value).await?;
 
-        resolved
-            .map(|items| items.users.remove(&id))
-            .flatten()
+        ctx.resolved()
+            .and_then(|items| items.users.remove(&id))
             .ok_or_else(|| error("User", true, "User expected"))
     }
 
@@ -256,11 +232,9 @@ impl<T: Send + Sync> Parse<T> for User {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Id<RoleMarker> {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Role(role)) = value {
             return Ok(*role);
@@ -276,17 +250,14 @@ impl<T: Send + Sync> Parse<T> for Id<RoleMarker> {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Role {
-    async fn parse(
-        http_client: &WrappedClient,
-        data: &T,
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        resolved: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
-        let id = <Id<RoleMarker> as Parse<T>>::parse(http_client, data, value, None).await?;
+        let id = <Id<RoleMarker> as Parse<T>>::parse_with(ctx, value).await?;
 
-        resolved
-            .map(|items| items.roles.remove(&id))
-            .flatten()
+        ctx.resolved()
+            .and_then(|items| items.roles.remove(&id))
             .ok_or_else(|| error("Role", true, "Role expected"))
     }
 
@@ -297,11 +268,9 @@ impl<T: Send + Sync> Parse<T> for Role {
 
 #[async_trait]
 impl<T: Send + Sync> Parse<T> for Id<GenericMarker> {
-    async fn parse(
-        _: &WrappedClient,
-        _: &T,
+    async fn parse_with(
+        _: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        _: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         if let Some(CommandOptionValue::Mentionable(id)) = value {
             return Ok(*id);
@@ -316,14 +285,12 @@ impl<T: Send + Sync> Parse<T> for Id<GenericMarker> {
 }
 
 #[async_trait]
-impl<T: Parse<E>, E: Send + Sync> Parse<E> for Option<T> {
-    async fn parse(
-        http_client: &WrappedClient,
-        data: &E,
+impl<T: Parse<E> + Send, E: Send + Sync> Parse<E> for Option<T> {
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, E>,
         value: Option<&CommandOptionValue>,
-        resolved: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
-        match T::parse(http_client, data, value, resolved).await {
+        match T::parse_with(ctx, value).await {
             Ok(parsed) => Ok(Some(parsed)),
             Err(mut why) => {
                 if value.is_some() {
@@ -359,18 +326,16 @@ impl<T: Parse<E>, E: Send + Sync> Parse<E> for Option<T> {
 #[async_trait]
 impl<T, E, C> Parse<C> for Result<T, E>
 where
-    T: Parse<C>,
-    E: From<ParseError>,
+    T: Parse<C> + Send,
+    E: From<ParseError> + Send,
     C: Send + Sync,
 {
-    async fn parse(
-        http_client: &WrappedClient,
-        data: &C,
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, C>,
         value: Option<&CommandOptionValue>,
-        resolved: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         // as we want to return the error if occurs, we'll map the error and always return Ok
-        Ok(T::parse(http_client, data, value, resolved)
+        Ok(T::parse_with(ctx, value)
             .await
             .map_err(From::from))
     }
@@ -397,13 +362,11 @@ macro_rules! impl_derived_parse {
         $($(
             #[async_trait]
             impl<T: Send + Sync> Parse<T> for $derived {
-                async fn parse(
-                    http_client: &WrappedClient,
-                    data: &T,
+                async fn parse_with(
+                    ctx: &mut ParseContext<'_, T>,
                     value: Option<&CommandOptionValue>,
-                    resolved: Option<&mut InteractionDataResolved>
                 ) -> Result<Self, ParseError> {
-                    let p = <$prim>::parse(http_client, data, value, resolved).await?;
+                    let p = <$prim>::parse_with(ctx, value).await?;
 
                     if p > <$derived>::MAX as $prim {
                         Err(error(
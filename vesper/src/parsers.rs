@@ -1,15 +1,15 @@
-use crate::builder::WrappedClient;
-use crate::parse::{Parse, ParseError};
+use crate::parse::{Parse, ParseContext, ParseError};
 use crate::parse_impl::error;
 use async_trait::async_trait;
 use std::ops::{Deref, DerefMut};
 use twilight_model::application::command::{CommandOption, CommandOptionType};
 use twilight_model::application::interaction::application_command::CommandOptionValue;
-use twilight_model::application::interaction::InteractionChannel;
-use twilight_model::application::interaction::InteractionDataResolved;
+use twilight_model::application::interaction::{InteractionChannel, InteractionMember};
 use twilight_model::channel::ChannelType;
-use twilight_model::id::marker::ChannelMarker;
+use twilight_model::guild::Role;
+use twilight_model::id::marker::{ChannelMarker, GenericMarker, UserMarker};
 use twilight_model::id::Id;
+use twilight_model::user::User;
 
 macro_rules! newtype_struct {
     ($($(#[$meta:meta])* $v: vis struct $name: ident($inner: ty)),* $(,)?) => {
@@ -46,13 +46,11 @@ macro_rules! parse_id {
     (@inner $name: ty, $kind: expr, [$($allowed: expr),* $(,)?]) => {
         #[async_trait]
         impl<T: Send + Sync> Parse<T> for $name {
-            async fn parse(
-                http_client: &WrappedClient,
-                data: &T,
+            async fn parse_with(
+                ctx: &mut ParseContext<'_, T>,
                 value: Option<&CommandOptionValue>,
-                resolved: Option<&mut InteractionDataResolved>
             ) -> Result<Self, ParseError> {
-                Ok(Self(Id::parse(http_client, data, value, resolved).await?))
+                Ok(Self(Id::parse_with(ctx, value).await?))
             }
 
             fn kind() -> CommandOptionType {
@@ -75,15 +73,13 @@ macro_rules! parse_derived_channel {
     (@inner $name_t: ty, $id: ty, $name: literal) => {
         #[async_trait]
         impl<T: Send + Sync> Parse<T> for $name_t {
-            async fn parse(
-                http_client: &WrappedClient,
-                data: &T,
+            async fn parse_with(
+                ctx: &mut ParseContext<'_, T>,
                 value: Option<&CommandOptionValue>,
-                resolved: Option<&mut InteractionDataResolved>
             ) -> Result<Self, ParseError> {
-                let id = <$id>::parse(http_client, data, value, None).await?;
+                let id = <$id>::parse_with(ctx, value).await?;
 
-                resolved.map(|items| items.channels.remove(&*id))
+                ctx.resolved().map(|items| items.channels.remove(&*id))
                     .flatten()
                     .ok_or_else(|| error($name, true, concat!($name, " expected")))
                     .map(Self)
@@ -138,3 +134,112 @@ parse_derived_channel! {
     PrivateThread, PrivateThreadId, "Private Thread",
     Thread, ThreadId, "Thread"
 }
+
+/// An object that parses into a member of the guild the command was used in, made of the given
+/// user and its member, which includes its nickname, roles, join date and permissions.
+///
+/// Parsing fails when used outside guilds or when the user is not a member of the guild, use
+/// [MaybeMember] to accept any user.
+#[derive(Clone, Debug)]
+pub struct Member {
+    /// The user given as argument.
+    pub user: User,
+    /// The member of the user in the guild the command was used in.
+    pub member: InteractionMember,
+}
+
+/// An object that parses into the given user and, when it is a member of the guild the command
+/// was used in, its member.
+#[derive(Clone, Debug)]
+pub struct MaybeMember {
+    /// The user given as argument.
+    pub user: User,
+    /// The member of the user, `None` outside guilds or if the user is not a member of the guild.
+    pub member: Option<InteractionMember>,
+}
+
+impl MaybeMember {
+    /// Converts this into a [Member], if the user is a member of the guild.
+    pub fn into_member(self) -> Option<Member> {
+        let Self { user, member } = self;
+        member.map(|member| Member { user, member })
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for MaybeMember {
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let id = <Id<UserMarker> as Parse<T>>::parse_with(ctx, value).await?;
+
+        let (user, member) = ctx.resolved()
+            .map(|items| (items.users.remove(&id), items.members.remove(&id)))
+            .unwrap_or_default();
+
+        Ok(Self {
+            user: user.ok_or_else(|| error("User", true, "User expected"))?,
+            member,
+        })
+    }
+
+    fn kind() -> CommandOptionType {
+        <Id<UserMarker> as Parse<T>>::kind()
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Member {
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        if ctx.guild_id.is_none() {
+            return Err(error("Member", true, "Members can only be used in guilds"));
+        }
+
+        <MaybeMember as Parse<T>>::parse_with(ctx, value)
+            .await?
+            .into_member()
+            .ok_or_else(|| error("Member", true, "User is not a member of this guild"))
+    }
+
+    fn kind() -> CommandOptionType {
+        <Id<UserMarker> as Parse<T>>::kind()
+    }
+}
+
+/// An object that parses into the user or the role given as a mentionable argument.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum Mentionable {
+    /// A user, with its member when it is a member of the guild the command was used in.
+    User(User, Option<InteractionMember>),
+    /// A role of the guild the command was used in.
+    Role(Role),
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Mentionable {
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let id = <Id<GenericMarker> as Parse<T>>::parse_with(ctx, value).await?;
+
+        ctx.resolved()
+            .and_then(|items| {
+                if let Some(user) = items.users.remove(&id.cast()) {
+                    Some(Self::User(user, items.members.remove(&id.cast())))
+                } else {
+                    items.roles.remove(&id.cast()).map(Self::Role)
+                }
+            })
+            .ok_or_else(|| error("Mentionable", true, "User or role expected"))
+    }
+
+    fn kind() -> CommandOptionType {
+        <Id<GenericMarker> as Parse<T>>::kind()
+    }
+}
@@ -7,12 +7,23 @@ use std::ops::{Deref, DerefMut};
 
 mod sealed {
     use super::*;
+    use twilight_model::application::command::CommandOptionValue;
 
     /// A trait used to specify the values [range](super::Range) can take.
     pub trait Number: Copy + Debug + Display {
         fn as_i64(&self) -> i64;
     }
 
+    /// A trait used to specify the values [float range](super::FloatRange) can take.
+    pub trait Float: Bounded {}
+
+    /// A trait used to specify the values [min](super::Min) and [max](super::Max) can take.
+    pub trait Bounded: Copy + Debug + Display {
+        fn as_f64(&self) -> f64;
+        fn min_value(bound: f64) -> CommandOptionValue;
+        fn max_value(bound: f64) -> CommandOptionValue;
+    }
+
     macro_rules! number {
         ($($t:ty),* $(,)?) => {
             $(
@@ -21,14 +32,56 @@ mod sealed {
                         *self as i64
                     }
                 }
+
+                impl Bounded for $t {
+                    fn as_f64(&self) -> f64 {
+                        *self as f64
+                    }
+
+                    fn min_value(bound: f64) -> CommandOptionValue {
+                        CommandOptionValue::Integer(bound.ceil() as i64)
+                    }
+
+                    fn max_value(bound: f64) -> CommandOptionValue {
+                        CommandOptionValue::Integer(bound.floor() as i64)
+                    }
+                }
+            )*
+        };
+    }
+
+    macro_rules! float {
+        ($($t:ty),* $(,)?) => {
+            $(
+                impl Float for $t {}
+
+                impl Bounded for $t {
+                    fn as_f64(&self) -> f64 {
+                        *self as f64
+                    }
+
+                    fn min_value(bound: f64) -> CommandOptionValue {
+                        CommandOptionValue::Number(bound)
+                    }
+
+                    fn max_value(bound: f64) -> CommandOptionValue {
+                        CommandOptionValue::Number(bound)
+                    }
+                }
             )*
         };
     }
 
     number![i8, i16, i32, i64, isize, u8, u16, u32, u64, usize];
+    float![f32, f64];
 }
 
-use sealed::Number;
+use sealed::{Bounded, Float, Number};
+
+/// Gets the value of a bound given as `VALUE / 10^SCALE`.
+fn bound(value: i64, scale: u32) -> f64 {
+    value as f64 / 10f64.powi(scale as i32)
+}
 
 /// A range-like type used to constraint the input provided by the user. This is equivalent to
 /// using a [RangeInclusive], but implements the [parse] trait.
@@ -55,15 +108,13 @@ impl<T: Number, const START: i64, const END: i64> DerefMut for Range<T, START, E
 impl<T, E, const START: i64, const END: i64> Parse<T> for Range<E, START, END>
 where
     T: Send + Sync,
-    E: Parse<T> + Number,
+    E: Parse<T> + Number + Send,
 {
-    async fn parse(
-        http_client: &WrappedClient,
-        data: &T,
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
         value: Option<&CommandOptionValue>,
-        resolved: Option<&mut InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
-        let value = E::parse(http_client, data, value, resolved).await?;
+        let value = E::parse_with(ctx, value).await?;
 
         let v = value.as_i64();
 
@@ -101,3 +152,250 @@ impl<T: Number, const START: i64, const END: i64> Debug for Range<T, START, END>
         )
     }
 }
+
+macro_rules! bounded_wrapper {
+    ($name: ident, $bound: ident, $($c: ident: $ct: ty),*) => {
+        impl<T: $bound, $(const $c: $ct),*> Deref for $name<T, $($c),*> {
+            type Target = T;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T: $bound, $(const $c: $ct),*> DerefMut for $name<T, $($c),*> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
+}
+
+/// A range-like type used to constraint floating point input provided by the user, from `START`
+/// to `END` both inclusive.
+///
+/// Since const generics can't be floats, the bounds are given as integers divided by
+/// `10^SCALE`, so `FloatRange<f64, -15, 25, 1>` accepts values from `-1.5` to `2.5`. The scale
+/// defaults to zero, making the bounds whole numbers.
+#[derive(Copy, Clone)]
+pub struct FloatRange<T: Float, const START: i64, const END: i64, const SCALE: u32 = 0>(T);
+
+bounded_wrapper!(FloatRange, Float, START: i64, END: i64, SCALE: u32);
+
+#[async_trait]
+impl<T, E, const START: i64, const END: i64, const SCALE: u32> Parse<T>
+    for FloatRange<E, START, END, SCALE>
+where
+    T: Send + Sync,
+    E: Parse<T> + Float + Send,
+{
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let value = E::parse_with(ctx, value).await?;
+
+        let v = value.as_f64();
+        let (start, end) = (bound(START, SCALE), bound(END, SCALE));
+
+        if v < start || v > end {
+            return Err(error(
+                &format!("FloatRange<{}, {}, {}>", type_name::<E>(), start, end),
+                true,
+                "Input out of range",
+            ));
+        }
+
+        Ok(Self(value))
+    }
+
+    fn kind() -> CommandOptionType {
+        E::kind()
+    }
+
+    fn modify_option(option: &mut CommandOption) {
+        option.max_value = Some(E::max_value(bound(END, SCALE)));
+        option.min_value = Some(E::min_value(bound(START, SCALE)));
+    }
+}
+
+impl<T: Float, const START: i64, const END: i64, const SCALE: u32> Debug
+    for FloatRange<T, START, END, SCALE>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "FloatRange<{}, {}, {}>({})",
+            type_name::<T>(),
+            bound(START, SCALE),
+            bound(END, SCALE),
+            self.0
+        )
+    }
+}
+
+/// A type used to constraint the input provided by the user to be at least `MIN`, given as
+/// `MIN / 10^SCALE` like the bounds of a [FloatRange]. The upper limit is the one of `T`.
+#[derive(Copy, Clone)]
+pub struct Min<T: Bounded, const MIN: i64, const SCALE: u32 = 0>(T);
+
+bounded_wrapper!(Min, Bounded, MIN: i64, SCALE: u32);
+
+#[async_trait]
+impl<T, E, const MIN: i64, const SCALE: u32> Parse<T> for Min<E, MIN, SCALE>
+where
+    T: Send + Sync,
+    E: Parse<T> + Bounded + Send,
+{
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let value = E::parse_with(ctx, value).await?;
+        let min = bound(MIN, SCALE);
+
+        if value.as_f64() < min {
+            return Err(error(
+                &format!("Min<{}, {}>", type_name::<E>(), min),
+                true,
+                "Input out of range",
+            ));
+        }
+
+        Ok(Self(value))
+    }
+
+    fn kind() -> CommandOptionType {
+        E::kind()
+    }
+
+    fn modify_option(option: &mut CommandOption) {
+        E::modify_option(option);
+        option.min_value = Some(E::min_value(bound(MIN, SCALE)));
+    }
+}
+
+impl<T: Bounded, const MIN: i64, const SCALE: u32> Debug for Min<T, MIN, SCALE> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Min<{}, {}>({})", type_name::<T>(), bound(MIN, SCALE), self.0)
+    }
+}
+
+/// A type used to constraint the input provided by the user to be at most `MAX`, given as
+/// `MAX / 10^SCALE` like the bounds of a [FloatRange]. The lower limit is the one of `T`.
+#[derive(Copy, Clone)]
+pub struct Max<T: Bounded, const MAX: i64, const SCALE: u32 = 0>(T);
+
+bounded_wrapper!(Max, Bounded, MAX: i64, SCALE: u32);
+
+#[async_trait]
+impl<T, E, const MAX: i64, const SCALE: u32> Parse<T> for Max<E, MAX, SCALE>
+where
+    T: Send + Sync,
+    E: Parse<T> + Bounded + Send,
+{
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let value = E::parse_with(ctx, value).await?;
+        let max = bound(MAX, SCALE);
+
+        if value.as_f64() > max {
+            return Err(error(
+                &format!("Max<{}, {}>", type_name::<E>(), max),
+                true,
+                "Input out of range",
+            ));
+        }
+
+        Ok(Self(value))
+    }
+
+    fn kind() -> CommandOptionType {
+        E::kind()
+    }
+
+    fn modify_option(option: &mut CommandOption) {
+        E::modify_option(option);
+        option.max_value = Some(E::max_value(bound(MAX, SCALE)));
+    }
+}
+
+impl<T: Bounded, const MAX: i64, const SCALE: u32> Debug for Max<T, MAX, SCALE> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Max<{}, {}>({})", type_name::<T>(), bound(MAX, SCALE), self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twilight_model::application::command::CommandOptionValue as OptionValue;
+
+    fn option(kind: CommandOptionType) -> CommandOption {
+        CommandOption {
+            autocomplete: None,
+            channel_types: None,
+            choices: None,
+            description: String::from("description"),
+            description_localizations: None,
+            kind,
+            max_length: None,
+            max_value: None,
+            min_length: None,
+            min_value: None,
+            name: String::from("name"),
+            name_localizations: None,
+            options: None,
+            required: None,
+        }
+    }
+
+    #[test]
+    fn bound_applies_scale() {
+        assert_eq!(bound(25, 0), 25.0);
+        assert_eq!(bound(15, 1), 1.5);
+        assert_eq!(bound(-25, 2), -0.25);
+    }
+
+    #[test]
+    fn integer_bounds_round_inwards() {
+        assert_eq!(<i64 as Bounded>::min_value(1.5), OptionValue::Integer(2));
+        assert_eq!(<i64 as Bounded>::max_value(1.5), OptionValue::Integer(1));
+        assert_eq!(<i64 as Bounded>::min_value(-1.5), OptionValue::Integer(-1));
+        assert_eq!(<i64 as Bounded>::max_value(-1.5), OptionValue::Integer(-2));
+        assert_eq!(<u8 as Bounded>::min_value(3.0), OptionValue::Integer(3));
+    }
+
+    #[test]
+    fn float_bounds_are_kept() {
+        assert_eq!(<f64 as Bounded>::min_value(1.5), OptionValue::Number(1.5));
+        assert_eq!(<f32 as Bounded>::max_value(-0.25), OptionValue::Number(-0.25));
+    }
+
+    #[test]
+    fn float_range_sets_scaled_bounds() {
+        let mut option = option(CommandOptionType::Number);
+        <FloatRange<f64, 5, 25, 1> as Parse<()>>::modify_option(&mut option);
+
+        assert_eq!(option.min_value, Some(OptionValue::Number(0.5)));
+        assert_eq!(option.max_value, Some(OptionValue::Number(2.5)));
+    }
+
+    #[test]
+    fn min_keeps_the_upper_bound_of_the_type() {
+        let mut option = option(CommandOptionType::Number);
+        <Min<f32, -15, 1> as Parse<()>>::modify_option(&mut option);
+
+        assert_eq!(option.min_value, Some(OptionValue::Number(-1.5)));
+        assert_eq!(option.max_value, Some(OptionValue::Number(f32::MAX as f64)));
+    }
+
+    #[test]
+    fn max_rounds_integer_bounds_down() {
+        let mut option = option(CommandOptionType::Integer);
+        <Max<i64, 15, 1> as Parse<()>>::modify_option(&mut option);
+
+        assert_eq!(option.max_value, Some(OptionValue::Integer(1)));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::twilight_exports::{
+    Component, DeserializeBodyError, InteractionResponseData, InteractionResponseType,
+};
+use thiserror::Error;
+use twilight_model::channel::message::{Embed, MessageFlags};
+
+/// Errors that can occur when responding an interaction using the [context](crate::context::SlashContext).
+#[derive(Debug, Error)]
+pub enum ResponseError {
+    /// An http error occurred.
+    #[error(transparent)]
+    Http(#[from] twilight_http::Error),
+    /// The response of discord could not be deserialized.
+    #[error(transparent)]
+    Deserialize(#[from] DeserializeBodyError),
+    /// The operation requires the interaction to be responded or deferred first.
+    #[error("The interaction has not been responded yet")]
+    NotResponded,
+}
+
+/// The state of the response of an interaction.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ResponseState {
+    /// The interaction has not been responded yet.
+    #[default]
+    Unanswered,
+    /// The interaction has been deferred, so the original response must be edited.
+    Deferred,
+    /// The interaction has been responded, further messages must be sent as followups.
+    Responded,
+}
+
+impl ResponseState {
+    /// Gets the state an interaction is left in after sending a response of the given kind.
+    pub(crate) fn after(kind: InteractionResponseType) -> Self {
+        match kind {
+            InteractionResponseType::DeferredChannelMessageWithSource
+            | InteractionResponseType::DeferredUpdateMessage => Self::Deferred,
+            _ => Self::Responded,
+        }
+    }
+
+    /// Returns whether the interaction has been acknowledged, either deferring or responding it.
+    pub fn is_acknowledged(&self) -> bool {
+        *self != Self::Unanswered
+    }
+}
+
+/// A message sent using [reply](crate::context::SlashContext::reply),
+/// [edit](crate::context::SlashContext::edit) or [followup](crate::context::SlashContext::followup).
+#[derive(Clone, Debug, Default)]
+pub struct Reply {
+    /// The content of the message.
+    pub content: Option<String>,
+    /// The embeds of the message.
+    pub embeds: Vec<Embed>,
+    /// The components of the message.
+    pub components: Vec<Component>,
+    /// Whether the message is only visible to the user who created the interaction.
+    pub ephemeral: bool,
+}
+
+impl Reply {
+    /// Creates an empty reply.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the content of the message.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Adds an embed to the message.
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.embeds.push(embed);
+        self
+    }
+
+    /// Sets the components of the message.
+    pub fn components(mut self, components: Vec<Component>) -> Self {
+        self.components = components;
+        self
+    }
+
+    /// Makes the message only visible to the user who created the interaction.
+    pub fn ephemeral(mut self) -> Self {
+        self.ephemeral = true;
+        self
+    }
+
+    pub(crate) fn flags(&self) -> Option<MessageFlags> {
+        self.ephemeral.then_some(MessageFlags::EPHEMERAL)
+    }
+
+    pub(crate) fn into_data(self) -> InteractionResponseData {
+        InteractionResponseData {
+            flags: self.flags(),
+            content: self.content,
+            embeds: Some(self.embeds),
+            components: Some(self.components),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<String> for Reply {
+    fn from(content: String) -> Self {
+        Self::new().content(content)
+    }
+}
+
+impl From<&str> for Reply {
+    fn from(content: &str) -> Self {
+        Self::new().content(content)
+    }
+}
+
+impl From<Embed> for Reply {
+    fn from(embed: Embed) -> Self {
+        Self::new().embed(embed)
+    }
+}
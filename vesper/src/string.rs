@@ -0,0 +1,241 @@
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// The maximum length of string arguments allowed by discord.
+const MAX_LENGTH: u16 = 6000;
+
+/// A string whose length, in characters, must be between `MIN` and `MAX` both inclusive. Discord
+/// enforces the limits on the client, and they are checked again when parsing.
+///
+/// `MAX` must be between 1 and 6000 and not lower than `MIN`, otherwise registering the command
+/// panics.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Length<const MIN: u16, const MAX: u16>(String);
+
+impl<const MIN: u16, const MAX: u16> Length<MIN, MAX> {
+    /// Consumes the wrapper, returning the inner string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const MIN: u16, const MAX: u16> Deref for Length<MIN, MAX> {
+    type Target = String;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MIN: u16, const MAX: u16> DerefMut for Length<MIN, MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[async_trait]
+impl<T, const MIN: u16, const MAX: u16> Parse<T> for Length<MIN, MAX>
+where
+    T: Send + Sync,
+{
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let value = String::parse_with(ctx, value).await?;
+        let length = value.chars().count();
+
+        if length < MIN as usize || length > MAX as usize {
+            return Err(error(
+                &format!("Length<{}, {}>", MIN, MAX),
+                true,
+                &format!("Input must be between {} and {} characters long", MIN, MAX),
+            ));
+        }
+
+        Ok(Self(value))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+
+    fn modify_option(option: &mut CommandOption) {
+        assert!(
+            MIN <= MAX && (1..=MAX_LENGTH).contains(&MAX),
+            "Length<{}, {}> must satisfy MIN <= MAX and 1 <= MAX <= {}",
+            MIN,
+            MAX,
+            MAX_LENGTH
+        );
+
+        option.min_length = Some(MIN);
+        option.max_length = Some(MAX);
+    }
+}
+
+impl<const MIN: u16, const MAX: u16> Debug for Length<MIN, MAX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Length<{}, {}>({:?})", MIN, MAX, self.0)
+    }
+}
+
+/// A validation applied to the input of a [Validated] argument.
+///
+/// ```rust
+/// use vesper::string::Validator;
+///
+/// struct Slug;
+///
+/// impl Validator for Slug {
+///     const NAME: &'static str = "Slug";
+///
+///     fn validate(value: &str) -> Result<(), String> {
+///         match value.chars().find(|c| !c.is_ascii_lowercase() && !c.is_ascii_digit() && *c != '-') {
+///             Some(c) => Err(format!("`{}` is not allowed, use lowercase letters, digits and `-`", c)),
+///             None => Ok(()),
+///         }
+///     }
+/// }
+/// ```
+pub trait Validator: Send + Sync + 'static {
+    /// The name of the argument type, shown in parsing errors.
+    const NAME: &'static str;
+
+    /// Checks the given input, returning why it is invalid otherwise.
+    fn validate(value: &str) -> Result<(), String>;
+
+    /// Modifies the option registered in discord, for example to set its length limits.
+    fn modify_option(_option: &mut CommandOption) {}
+}
+
+/// A string accepted by the [validator](Validator) `V`.
+pub struct Validated<V: Validator>(String, PhantomData<V>);
+
+impl<V: Validator> Validated<V> {
+    /// Consumes the wrapper, returning the inner string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<V: Validator> Clone for Validated<V> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<V: Validator> Deref for Validated<V> {
+    type Target = String;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<T, V> Parse<T> for Validated<V>
+where
+    T: Send + Sync,
+    V: Validator,
+{
+    async fn parse_with(
+        ctx: &mut ParseContext<'_, T>,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let value = String::parse_with(ctx, value).await?;
+
+        V::validate(&value).map_err(|why| error(V::NAME, true, &why))?;
+
+        Ok(Self(value, PhantomData))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+
+    fn modify_option(option: &mut CommandOption) {
+        V::modify_option(option)
+    }
+}
+
+impl<V: Validator> Debug for Validated<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Validated<{}>({:?})", V::NAME, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lowercase;
+
+    impl Validator for Lowercase {
+        const NAME: &'static str = "Lowercase";
+
+        fn validate(_: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn modify_option(option: &mut CommandOption) {
+            option.max_length = Some(20);
+        }
+    }
+
+    fn option() -> CommandOption {
+        CommandOption {
+            autocomplete: None,
+            channel_types: None,
+            choices: None,
+            description: String::from("description"),
+            description_localizations: None,
+            kind: CommandOptionType::String,
+            max_length: None,
+            max_value: None,
+            min_length: None,
+            min_value: None,
+            name: String::from("name"),
+            name_localizations: None,
+            options: None,
+            required: None,
+        }
+    }
+
+    #[test]
+    fn length_sets_the_option_limits() {
+        let mut option = option();
+        <Length<2, 6000> as Parse<()>>::modify_option(&mut option);
+
+        assert_eq!(option.min_length, Some(2));
+        assert_eq!(option.max_length, Some(6000));
+    }
+
+    #[test]
+    #[should_panic(expected = "MIN <= MAX")]
+    fn length_rejects_inverted_bounds() {
+        <Length<10, 5> as Parse<()>>::modify_option(&mut option());
+    }
+
+    #[test]
+    #[should_panic(expected = "MIN <= MAX")]
+    fn length_rejects_a_zero_maximum() {
+        <Length<0, 0> as Parse<()>>::modify_option(&mut option());
+    }
+
+    #[test]
+    #[should_panic(expected = "MIN <= MAX")]
+    fn length_rejects_maximums_over_the_limit() {
+        <Length<1, 6001> as Parse<()>>::modify_option(&mut option());
+    }
+
+    #[test]
+    fn validated_uses_the_validator_option() {
+        let mut option = option();
+        <Validated<Lowercase> as Parse<()>>::modify_option(&mut option);
+
+        assert_eq!(option.max_length, Some(20));
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{
+    framework::Framework,
+    prelude::CreateCommandError,
+    twilight_exports::{
+        Command as TwilightCommand, CommandOption, CommandOptionType, CommandType, GuildMarker,
+        Id, Permissions,
+    },
+};
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// The scope in which commands are synchronized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyncScope {
+    /// Commands registered globally, available in every guild and in direct messages.
+    Global,
+    /// Commands registered only in the given guild.
+    Guild(Id<GuildMarker>),
+}
+
+/// A command whose definition differs between Discord and the framework.
+#[derive(Clone, Debug)]
+pub struct CommandChange {
+    /// The command as currently registered in Discord.
+    pub current: TwilightCommand,
+    /// The command as the framework would register it.
+    pub desired: TwilightCommand,
+}
+
+/// The differences between the commands registered in Discord and the ones registered
+/// in the framework, as returned by [`Framework::diff_commands`] and [`Framework::sync_commands`].
+#[derive(Clone, Debug, Default)]
+pub struct CommandDiff {
+    /// Commands present in the framework but not registered in Discord.
+    pub added: Vec<TwilightCommand>,
+    /// Commands registered in both places whose definition changed.
+    pub changed: Vec<CommandChange>,
+    /// Commands registered in Discord that are no longer present in the framework.
+    pub removed: Vec<TwilightCommand>,
+    /// Names of the commands that are already up to date.
+    pub unchanged: Vec<String>,
+}
+
+impl CommandDiff {
+    /// Returns `true` if Discord is already up to date with the framework.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<D, T, E> Framework<D, T, E> {
+    /// Computes the differences between the commands registered in Discord for the given
+    /// [scope](SyncScope) and the commands registered in the framework, without modifying anything.
+    ///
+    /// This can be used as a dry-run of [`sync_commands`](Self::sync_commands).
+    pub async fn diff_commands(&self, scope: SyncScope) -> Result<CommandDiff, CreateCommandError> {
+        let current = self.fetch_commands(scope).await?;
+        let desired = self.scoped_commands(scope);
+
+        Ok(compute_diff(current, desired, scope))
+    }
+
+    /// Synchronizes the commands registered in Discord for the given [scope](SyncScope) with the
+    /// commands registered in the framework.
+    ///
+    /// Commands are fetched from Discord and compared with the ones the framework would register,
+    /// if anything was added, changed or removed, the whole set is applied using Discord's bulk
+    /// overwrite endpoint, so commands removed from the framework are deleted too. If everything
+    /// is up to date, no request apart from the fetch is made.
+    ///
+    /// The returned [diff](CommandDiff) contains what has been applied.
+    pub async fn sync_commands(&self, scope: SyncScope) -> Result<CommandDiff, CreateCommandError> {
+        let current = self.fetch_commands(scope).await?;
+        let desired = self.scoped_commands(scope);
+
+        for command in &desired {
+            twilight_validate::command::command(command)?;
+        }
+
+        let diff = compute_diff(current, desired.clone(), scope);
+
+        if diff.is_empty() {
+            debug!("Commands at {:?} are up to date, skipping synchronization", scope);
+            return Ok(diff);
+        }
+
+        info!(
+            "Synchronizing commands at {:?}: {} added, {} changed, {} removed",
+            scope,
+            diff.added.len(),
+            diff.changed.len(),
+            diff.removed.len()
+        );
+
+        let client = self.interaction_client();
+        match scope {
+            SyncScope::Global => {
+                client.set_global_commands(&desired).await?.models().await?;
+            }
+            SyncScope::Guild(guild_id) => {
+                client.set_guild_commands(guild_id, &desired).await?.models().await?;
+            }
+        }
+
+        Ok(diff)
+    }
+
+    /// Fetches the commands currently registered in Discord for the given scope.
+    async fn fetch_commands(&self, scope: SyncScope) -> Result<Vec<TwilightCommand>, CreateCommandError> {
+        let client = self.interaction_client();
+        let commands = match scope {
+            SyncScope::Global => client.global_commands().await?.models().await?,
+            SyncScope::Guild(guild_id) => client.guild_commands(guild_id).await?.models().await?,
+        };
+
+        Ok(commands)
+    }
+
+    /// Gets the commands of the framework as they must be registered in the given scope.
+    #[allow(deprecated)]
+    fn scoped_commands(&self, scope: SyncScope) -> Vec<TwilightCommand> {
+        let mut commands = self.twilight_commands();
+
+        if let SyncScope::Guild(guild_id) = scope {
+            for command in &mut commands {
+                // Guild commands can't be used in direct messages.
+                command.dm_permission = None;
+                command.guild_id = Some(guild_id);
+            }
+        }
+
+        commands
+    }
+}
+
+/// The fields of a command taken into account when comparing two definitions, Discord omits
+/// default values when returning commands, so all of them are normalized beforehand.
+#[derive(PartialEq)]
+struct CommandSignature<'a> {
+    description: &'a str,
+    default_member_permissions: Option<Permissions>,
+    dm_permission: bool,
+    nsfw: bool,
+    name_localizations: Option<&'a HashMap<String, String>>,
+    description_localizations: Option<&'a HashMap<String, String>>,
+    options: Vec<CommandOption>,
+}
+
+impl<'a> CommandSignature<'a> {
+    #[allow(deprecated)]
+    fn new(command: &'a TwilightCommand, scope: SyncScope) -> Self {
+        Self {
+            // Only chat input commands have a description.
+            description: if command.kind == CommandType::ChatInput {
+                &command.description
+            } else {
+                ""
+            },
+            default_member_permissions: command.default_member_permissions,
+            dm_permission: match scope {
+                SyncScope::Global => command.dm_permission.unwrap_or(true),
+                SyncScope::Guild(_) => false,
+            },
+            nsfw: command.nsfw.unwrap_or(false),
+            name_localizations: non_empty(&command.name_localizations),
+            description_localizations: non_empty(&command.description_localizations),
+            options: normalize_options(command.options.clone()),
+        }
+    }
+}
+
+fn non_empty(map: &Option<HashMap<String, String>>) -> Option<&HashMap<String, String>> {
+    map.as_ref().filter(|map| !map.is_empty())
+}
+
+fn normalize_options(mut options: Vec<CommandOption>) -> Vec<CommandOption> {
+    // Subcommands and subcommand groups are stored in maps, so their order is not meaningful.
+    if options.iter().all(|option| {
+        matches!(option.kind, CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup)
+    }) {
+        options.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    options.into_iter().map(normalize_option).collect()
+}
+
+fn normalize_option(mut option: CommandOption) -> CommandOption {
+    option.autocomplete = option.autocomplete.filter(|autocomplete| *autocomplete);
+    option.required = option.required.filter(|required| *required);
+    option.choices = option.choices.filter(|choices| !choices.is_empty());
+    option.channel_types = option.channel_types.filter(|types| !types.is_empty());
+    option.name_localizations = option.name_localizations.filter(|map| !map.is_empty());
+    option.description_localizations = option
+        .description_localizations
+        .filter(|map| !map.is_empty());
+    option.options = option
+        .options
+        .map(normalize_options)
+        .filter(|options| !options.is_empty());
+
+    option
+}
+
+/// Compares the current commands against the desired ones, matching them by name and type.
+fn compute_diff(
+    current: Vec<TwilightCommand>,
+    desired: Vec<TwilightCommand>,
+    scope: SyncScope,
+) -> CommandDiff {
+    let mut diff = CommandDiff::default();
+    let mut current = current
+        .into_iter()
+        .map(|command| ((command.name.clone(), command.kind), command))
+        .collect::<HashMap<_, _>>();
+
+    for command in desired {
+        match current.remove(&(command.name.clone(), command.kind)) {
+            None => diff.added.push(command),
+            Some(existing) => {
+                if CommandSignature::new(&existing, scope) == CommandSignature::new(&command, scope) {
+                    diff.unchanged.push(command.name);
+                } else {
+                    diff.changed.push(CommandChange {
+                        current: existing,
+                        desired: command,
+                    });
+                }
+            }
+        }
+    }
+
+    diff.removed.extend(current.into_values());
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twilight_util::builder::command::{
+        CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+    };
+
+    fn command(name: &str, description: &str, options: Vec<CommandOption>) -> TwilightCommand {
+        let mut builder = CommandBuilder::new(name, description, CommandType::ChatInput);
+        for option in options {
+            builder = builder.option(option);
+        }
+
+        builder.build()
+    }
+
+    fn subcommand(name: &str) -> CommandOption {
+        SubCommandBuilder::new(name, "A subcommand")
+            .option(StringBuilder::new("text", "Some text").required(true))
+            .build()
+    }
+
+    /// Simulates Discord returning a command, which omits the default values of its fields.
+    #[allow(deprecated)]
+    fn registered(mut command: TwilightCommand) -> TwilightCommand {
+        command.dm_permission = None;
+        command.nsfw = None;
+        command.name_localizations = None;
+        command.description_localizations = None;
+        for option in &mut command.options {
+            option.required = option.required.filter(|required| *required);
+            option.autocomplete = None;
+        }
+
+        command
+    }
+
+    #[test]
+    fn keeps_unchanged_commands() {
+        let text = StringBuilder::new("text", "Some text").autocomplete(false).build();
+        let desired = command("ping", "Pong", vec![text]);
+        let current = registered(desired.clone());
+
+        let diff = compute_diff(vec![current], vec![desired], SyncScope::Global);
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.unchanged, vec![String::from("ping")]);
+    }
+
+    #[test]
+    fn ignores_the_order_of_subcommands() {
+        let desired = command("config", "Configure", vec![subcommand("get"), subcommand("set")]);
+        let current = command("config", "Configure", vec![subcommand("set"), subcommand("get")]);
+
+        let diff = compute_diff(vec![current], vec![desired], SyncScope::Global);
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn keeps_the_order_of_arguments() {
+        let text = || StringBuilder::new("text", "Some text").build();
+        let number = || IntegerBuilder::new("number", "A number").build();
+        let desired = command("echo", "Echo", vec![text(), number()]);
+        let current = command("echo", "Echo", vec![number(), text()]);
+
+        let diff = compute_diff(vec![current], vec![desired], SyncScope::Global);
+
+        assert_eq!(diff.changed.len(), 1);
+    }
+
+    #[test]
+    fn detects_changed_descriptions() {
+        let desired = command("ping", "Pong", Vec::new());
+        let current = command("ping", "Ping", Vec::new());
+
+        let diff = compute_diff(vec![current], vec![desired], SyncScope::Global);
+
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].current.description, "Ping");
+        assert_eq!(diff.changed[0].desired.description, "Pong");
+        assert!(diff.unchanged.is_empty());
+    }
+
+    #[test]
+    fn detects_added_and_removed_commands() {
+        let diff = compute_diff(
+            vec![command("ping", "Pong", Vec::new())],
+            vec![command("echo", "Echo", Vec::new())],
+            SyncScope::Global,
+        );
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "echo");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "ping");
+    }
+
+    #[test]
+    fn normalizes_default_option_values() {
+        let option = StringBuilder::new("text", "Some text")
+            .required(false)
+            .autocomplete(false)
+            .build();
+        let mut omitted = option.clone();
+        omitted.required = None;
+        omitted.autocomplete = None;
+        omitted.choices = Some(Vec::new());
+
+        assert_eq!(normalize_options(vec![option]), normalize_options(vec![omitted]));
+    }
+}
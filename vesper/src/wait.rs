@@ -1,32 +1,65 @@
-use std::{future::Future, task::{Context, Poll}};
+use std::{future::Future, task::{Context, Poll}, time::Duration};
 use std::pin::Pin;
-use tokio::sync::oneshot::{Sender, Receiver, channel, error::RecvError};
+use std::sync::{atomic::{AtomicU64, Ordering}, Arc, Weak};
+use parking_lot::Mutex;
+use thiserror::Error;
+use tokio::sync::{mpsc::UnboundedSender, oneshot::{Sender, Receiver, channel, error::RecvError}};
+use tokio::time::Sleep;
 use crate::twilight_exports::Interaction;
 
-pub(crate) fn new_pair<F>(fun: F) -> (WaiterWaker, InteractionWaiter)
+/// The id given to the next waiter created.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the id to be used by a new waker.
+pub(crate) fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+pub(crate) fn new_pair<F>(waiters: &Arc<Mutex<Vec<WaiterWaker>>>, fun: F) -> (WaiterWaker, InteractionWaiter)
 where
     F: Fn(&Interaction) -> bool + Send + 'static
 {
     let (sender, receiver) = channel();
+    let id = next_id();
 
     (
         WaiterWaker {
+            id,
             predicate: Box::new(fun),
-            sender
+            sender: WakerSender::Once(sender)
         },
         InteractionWaiter {
-            receiver
+            id,
+            receiver,
+            deadline: None,
+            waiters: Arc::downgrade(waiters)
         }
     )
 }
 
+/// Errors that can be returned when awaiting a [waiter](InteractionWaiter).
+#[derive(Debug, Error)]
+pub enum WaitError {
+    /// The waiter did not receive any interaction before its timeout.
+    #[error("The waiter timed out")]
+    Timeout,
+    /// The waker of the waiter was dropped without delivering any interaction.
+    #[error(transparent)]
+    Closed(#[from] RecvError)
+}
+
 /// A waiter used to wait for an interaction.
 ///
 /// The waiter implements [`Future`], so in order to retrieve the interaction, just await the waiter.
+/// By default the waiter waits forever, a deadline can be set using [timeout](Self::timeout).
+///
+/// Dropping the waiter cancels it, removing it from the framework. The waiter does not borrow the
+/// context, so it can be moved into a spawned task.
 ///
 /// # Examples:
 ///
 /// ```rust
+/// use std::time::Duration;
 /// use vesper::prelude::{command, SlashContext, DefaultCommandResult};
 ///
 /// #[command]
@@ -36,41 +69,171 @@ where
 ///     let interaction = ctx.wait_interaction(|interaction| {
 ///         // predicate here
 ///         false
-///     }).await?;
+///     })
+///     .timeout(Duration::from_secs(60))
+///     .await?;
 ///
 ///     Ok(())
 /// }
 /// ```
 ///
 /// [`Future`]: Future
+#[must_use = "Waiters do nothing unless awaited"]
 pub struct InteractionWaiter {
-    receiver: Receiver<Interaction>
+    id: u64,
+    receiver: Receiver<Interaction>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    waiters: Weak<Mutex<Vec<WaiterWaker>>>
+}
+
+impl InteractionWaiter {
+    /// Sets the maximum time to wait for the interaction, after which the waiter resolves to
+    /// [WaitError::Timeout].
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.deadline = Some(Box::pin(tokio::time::sleep(duration)));
+        self
+    }
+
+    /// Removes the waker of this waiter from the framework.
+    fn cancel(&self) {
+        remove_waker(&self.waiters, self.id);
+    }
 }
 
 impl Future for InteractionWaiter {
-    type Output = Result<Interaction, RecvError>;
+    type Output = Result<Interaction, WaitError>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        Pin::new(&mut self.receiver).poll(cx)
+        if let Poll::Ready(result) = Pin::new(&mut self.receiver).poll(cx) {
+            return Poll::Ready(result.map_err(WaitError::from));
+        }
+
+        if let Some(deadline) = self.deadline.as_mut() {
+            if deadline.as_mut().poll(cx).is_ready() {
+                self.cancel();
+                return Poll::Ready(Err(WaitError::Timeout));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for InteractionWaiter {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+
+/// Removes the waker with the given id from the framework, if it still exists.
+pub(crate) fn remove_waker(waiters: &Weak<Mutex<Vec<WaiterWaker>>>, id: u64) {
+    if let Some(waiters) = waiters.upgrade() {
+        waiters.lock().retain(|waker| waker.id != id);
     }
 }
 
+/// The channel used by a [`waker`] to deliver interactions.
+///
+/// [`waker`]: WaiterWaker
+pub enum WakerSender {
+    /// Delivers a single interaction to an [`InteractionWaiter`].
+    Once(Sender<Interaction>),
+    /// Delivers every matching interaction to a
+    /// [`ComponentCollector`](crate::collector::ComponentCollector).
+    Stream(UnboundedSender<Interaction>)
+}
 
 /// A waker used to notify its associate [`waiter`] when the predicate has been satisfied and
 /// deliver the interaction.
 ///
 /// [`waiter`]: InteractionWaiter
 pub struct WaiterWaker {
+    pub(crate) id: u64,
     pub predicate: Box<dyn Fn(&Interaction) -> bool + Send + 'static>,
-    pub sender: Sender<Interaction>
+    pub sender: WakerSender
 }
 
 impl WaiterWaker {
     pub fn check(&self, interaction: &Interaction) -> bool {
-        (self.predicate)(interaction)
+        !self.is_closed() && (self.predicate)(interaction)
+    }
+
+    /// Returns whether the associated [`waiter`] has been dropped.
+    ///
+    /// [`waiter`]: InteractionWaiter
+    pub fn is_closed(&self) -> bool {
+        match &self.sender {
+            WakerSender::Once(sender) => sender.is_closed(),
+            WakerSender::Stream(sender) => sender.is_closed()
+        }
+    }
+
+    /// Returns whether the waker keeps receiving interactions after being woken.
+    pub fn is_persistent(&self) -> bool {
+        matches!(self.sender, WakerSender::Stream(_))
+    }
+
+    /// Delivers the interaction to a persistent waker, keeping it registered.
+    pub(crate) fn deliver(&self, interaction: Interaction) {
+        if let WakerSender::Stream(sender) = &self.sender {
+            let _ = sender.send(interaction);
+        }
     }
 
     pub fn wake(self, interaction: Interaction) {
-        let _ = self.sender.send(interaction);
+        match self.sender {
+            WakerSender::Once(sender) => {
+                let _ = sender.send(interaction);
+            },
+            WakerSender::Stream(sender) => {
+                let _ = sender.send(interaction);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waiters() -> Arc<Mutex<Vec<WaiterWaker>>> {
+        Arc::new(Mutex::new(Vec::new()))
+    }
+
+    fn register(waiters: &Arc<Mutex<Vec<WaiterWaker>>>) -> InteractionWaiter {
+        let (waker, waiter) = new_pair(waiters, |_| false);
+        waiters.lock().push(waker);
+        waiter
+    }
+
+    #[tokio::test]
+    async fn waiters_can_be_moved_into_tasks() {
+        let waiters = waiters();
+        let waiter = register(&waiters);
+
+        let task = tokio::spawn(waiter);
+        drop(waiters);
+
+        assert!(matches!(task.await.unwrap(), Err(WaitError::Closed(_))));
+    }
+
+    #[tokio::test]
+    async fn timeouts_remove_the_waker() {
+        let waiters = waiters();
+        let waiter = register(&waiters).timeout(Duration::from_millis(10));
+
+        assert!(matches!(waiter.await, Err(WaitError::Timeout)));
+        assert!(waiters.lock().is_empty());
+    }
+
+    #[test]
+    fn dropping_the_waiter_removes_the_waker() {
+        let waiters = waiters();
+        let waiter = register(&waiters);
+        assert_eq!(waiters.lock().len(), 1);
+
+        drop(waiter);
+        assert!(waiters.lock().is_empty());
     }
 }